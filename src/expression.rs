@@ -0,0 +1,436 @@
+use serde_json::{json, Value};
+
+// Expressions of x-expression : arithmetic (+ - * / %), comparison (== != < <= > >=), logic (&& || !),
+// conditional (cond ? a : b), string concatenation with +, literals ('text', "text", 1.5, true, false, null),
+// field paths (quantity, items.total) and the functions sum, count, avg, min, max, round, abs and if.
+// Paths that cross arrays collect the values of every item, so sum(items.total) adds the column of the child rows.
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+}
+
+#[derive(Debug)]
+enum Expr {
+    Literal(Value),
+    Path(String),
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Conditional(Box<Expr>, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+const OPERATORS: [&str; 20] = ["&&", "||", "==", "!=", "<=", ">=", "<", ">", "+", "-", "*", "/", "%", "!", "?", ":", "(", ")", ",", "="];
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, Box<dyn std::error::Error>> {
+    let chars = expression.chars().collect::<Vec<char>>();
+    let mut tokens = vec![];
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+
+        if c.is_whitespace() {
+            pos += 1;
+        } else if c.is_ascii_digit() || (c == '.' && chars.get(pos + 1).is_some_and(|c| c.is_ascii_digit())) {
+            let start = pos;
+
+            while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+                pos += 1;
+            }
+
+            // exponent, as in 1e3 or 2.5E-2
+            if pos < chars.len() && (chars[pos] == 'e' || chars[pos] == 'E') {
+                let digits = if chars.get(pos + 1).is_some_and(|c| *c == '+' || *c == '-') { pos + 2 } else { pos + 1 };
+
+                if chars.get(digits).is_some_and(|c| c.is_ascii_digit()) {
+                    pos = digits;
+
+                    while pos < chars.len() && chars[pos].is_ascii_digit() {
+                        pos += 1;
+                    }
+                }
+            }
+
+            let str = chars[start..pos].iter().collect::<String>();
+            tokens.push(Token::Number(str.parse::<f64>()?));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = pos;
+
+            while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '_' || chars[pos] == '.') {
+                pos += 1;
+            }
+
+            tokens.push(Token::Ident(chars[start..pos].iter().collect()));
+        } else if c == '\'' || c == '"' {
+            let start = pos + 1;
+            pos = start;
+
+            while pos < chars.len() && chars[pos] != c {
+                pos += 1;
+            }
+
+            if pos >= chars.len() {
+                Err(format!("Unterminated string in expression {}", expression))?;
+            }
+
+            tokens.push(Token::Str(chars[start..pos].iter().collect()));
+            pos += 1;
+        } else {
+            let rest = chars[pos..].iter().take(2).collect::<String>();
+            let op = OPERATORS.iter().find(|op| rest.starts_with(*op)).ok_or(format!("Unexpected char '{}' in expression {}", c, expression))?;
+            tokens.push(Token::Op(op));
+            pos += op.len();
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_op(&self, ops: &[&'static str]) -> Option<&'static str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) if ops.contains(op) => Some(op),
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, op: &str) -> Result<(), Box<dyn std::error::Error>> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(item)) if *item == op => {
+                self.pos += 1;
+                Ok(())
+            }
+            token => Err(format!("Expected '{}' at {:?}", op, token))?,
+        }
+    }
+
+    fn conditional(&mut self) -> Result<Expr, Box<dyn std::error::Error>> {
+        let condition = self.binary(0)?;
+
+        if self.peek_op(&["?"]).is_none() {
+            return Ok(condition);
+        }
+
+        self.pos += 1;
+        let value_true = self.conditional()?;
+        self.expect(":")?;
+        let value_false = self.conditional()?;
+        Ok(Expr::Conditional(Box::new(condition), Box::new(value_true), Box::new(value_false)))
+    }
+
+    // precedence levels, from the lowest
+    fn binary(&mut self, level: usize) -> Result<Expr, Box<dyn std::error::Error>> {
+        const LEVELS: [&[&str]; 5] = [&["||"], &["&&"], &["==", "!=", "=", "<", "<=", ">", ">="], &["+", "-"], &["*", "/", "%"]];
+
+        if level >= LEVELS.len() {
+            return self.unary();
+        }
+
+        let mut left = self.binary(level + 1)?;
+
+        while let Some(op) = self.peek_op(LEVELS[level]) {
+            self.pos += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(if op == "=" { "==" } else { op }, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Box<dyn std::error::Error>> {
+        if let Some(op) = self.peek_op(&["-", "!"]) {
+            self.pos += 1;
+            return Ok(Expr::Unary(op, Box::new(self.unary()?)));
+        }
+
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, Box<dyn std::error::Error>> {
+        let token = self.tokens.get(self.pos).cloned().ok_or("Unexpected end of expression")?;
+        self.pos += 1;
+
+        match token {
+            Token::Number(value) => Ok(Expr::Literal(from_f64(value))),
+            Token::Str(value) => Ok(Expr::Literal(json!(value))),
+            Token::Ident(name) if name == "true" => Ok(Expr::Literal(json!(true))),
+            Token::Ident(name) if name == "false" => Ok(Expr::Literal(json!(false))),
+            Token::Ident(name) if name == "null" => Ok(Expr::Literal(Value::Null)),
+            Token::Ident(name) => {
+                if self.peek_op(&["("]).is_none() {
+                    return Ok(Expr::Path(name));
+                }
+
+                self.pos += 1;
+                let mut args = vec![];
+
+                if self.peek_op(&[")"]).is_none() {
+                    loop {
+                        args.push(self.conditional()?);
+
+                        if self.peek_op(&[","]).is_none() {
+                            break;
+                        }
+
+                        self.pos += 1;
+                    }
+                }
+
+                self.expect(")")?;
+                Ok(Expr::Call(name, args))
+            }
+            Token::Op("(") => {
+                let expr = self.conditional()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Token::Op(op) => Err(format!("Unexpected '{}' in expression", op))?,
+        }
+    }
+}
+
+fn to_f64(value: &Value) -> f64 {
+    match value {
+        Value::Number(number) => number.as_f64().unwrap_or(0.0),
+        Value::String(str) => str.parse::<f64>().unwrap_or(0.0),
+        Value::Bool(true) => 1.0,
+        _ => 0.0,
+    }
+}
+
+fn to_string(value: &Value) -> String {
+    match value {
+        Value::String(str) => str.clone(),
+        Value::Null => "".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+pub fn is_true(value: &Value) -> bool {
+    match value {
+        Value::Bool(value) => *value,
+        Value::Number(_) => to_f64(value) != 0.0,
+        Value::String(str) => !str.is_empty(),
+        Value::Array(list) => !list.is_empty(),
+        Value::Object(_) => true,
+        Value::Null => false,
+    }
+}
+
+// integral results are kept as integers, to match integer fields and compare equal to them
+fn from_f64(value: f64) -> Value {
+    if value.is_finite() && value.fract() == 0.0 && value.abs() < 9007199254740992.0 {
+        json!(value as i64)
+    } else {
+        json!(value)
+    }
+}
+
+fn to_list(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(list) => list,
+        Value::Null => vec![],
+        value => vec![value],
+    }
+}
+
+fn resolve(context: &Value, path: &str) -> Value {
+    let mut value = context.clone();
+
+    for name in path.split('.') {
+        value = match value {
+            Value::Array(list) => Value::Array(list.iter().map(|item| item.get(name).cloned().unwrap_or(Value::Null)).collect()),
+            value => value.get(name).cloned().unwrap_or(Value::Null),
+        };
+    }
+
+    value
+}
+
+pub fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(_), Value::Number(_)) => to_f64(a) == to_f64(b),
+        _ => a == b,
+    }
+}
+
+fn eval(expr: &Expr, context: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+    let value = match expr {
+        Expr::Literal(value) => value.clone(),
+        Expr::Path(path) => resolve(context, path),
+        Expr::Unary(op, expr) => {
+            let value = eval(expr, context)?;
+
+            match *op {
+                "-" => from_f64(-to_f64(&value)),
+                _ => json!(!is_true(&value)),
+            }
+        }
+        Expr::Conditional(condition, value_true, value_false) => {
+            if is_true(&eval(condition, context)?) {
+                eval(value_true, context)?
+            } else {
+                eval(value_false, context)?
+            }
+        }
+        Expr::Binary(op, left, right) => {
+            let left = eval(left, context)?;
+
+            match *op {
+                "&&" => return Ok(json!(is_true(&left) && is_true(&eval(right, context)?))),
+                "||" => return Ok(json!(is_true(&left) || is_true(&eval(right, context)?))),
+                _ => {}
+            }
+
+            let right = eval(right, context)?;
+
+            match *op {
+                "+" if left.is_string() || right.is_string() => json!(format!("{}{}", to_string(&left), to_string(&right))),
+                "+" => from_f64(to_f64(&left) + to_f64(&right)),
+                "-" => from_f64(to_f64(&left) - to_f64(&right)),
+                "*" => from_f64(to_f64(&left) * to_f64(&right)),
+                "/" => {
+                    let divisor = to_f64(&right);
+
+                    if divisor == 0.0 {
+                        Value::Null
+                    } else {
+                        from_f64(to_f64(&left) / divisor)
+                    }
+                }
+                "%" => from_f64(to_f64(&left) % to_f64(&right)),
+                "==" => json!(values_equal(&left, &right)),
+                "!=" => json!(!values_equal(&left, &right)),
+                _ => {
+                    let ordering = if left.is_string() && right.is_string() {
+                        to_string(&left).cmp(&to_string(&right))
+                    } else {
+                        to_f64(&left).partial_cmp(&to_f64(&right)).unwrap_or(std::cmp::Ordering::Equal)
+                    };
+
+                    match *op {
+                        "<" => json!(ordering.is_lt()),
+                        "<=" => json!(ordering.is_le()),
+                        ">" => json!(ordering.is_gt()),
+                        _ => json!(ordering.is_ge()),
+                    }
+                }
+            }
+        }
+        Expr::Call(name, args) => {
+            if name == "if" {
+                if args.len() != 3 {
+                    Err("if expects 3 arguments")?;
+                }
+
+                return if is_true(&eval(&args[0], context)?) { eval(&args[1], context) } else { eval(&args[2], context) };
+            }
+
+            let mut values = vec![];
+
+            for arg in args {
+                values.push(eval(arg, context)?);
+            }
+
+            let first = values.first().cloned().unwrap_or(Value::Null);
+            let numbers = to_list(first.clone()).iter().filter(|value| !value.is_null()).map(to_f64).collect::<Vec<f64>>();
+
+            match name.as_str() {
+                "sum" => from_f64(numbers.iter().sum()),
+                "count" => json!(to_list(first).len()),
+                "avg" if numbers.is_empty() => Value::Null,
+                "avg" => from_f64(numbers.iter().sum::<f64>() / numbers.len() as f64),
+                "min" => numbers.iter().cloned().reduce(f64::min).map(from_f64).unwrap_or(Value::Null),
+                "max" => numbers.iter().cloned().reduce(f64::max).map(from_f64).unwrap_or(Value::Null),
+                "abs" => from_f64(to_f64(&first).abs()),
+                "round" => {
+                    let scale = values.get(1).map(to_f64).unwrap_or(0.0) as i32;
+                    let factor = 10f64.powi(scale);
+                    from_f64((to_f64(&first) * factor).round() / factor)
+                }
+                _ => Err(format!("Unknown function {}", name))?,
+            }
+        }
+    };
+
+    Ok(value)
+}
+
+pub fn evaluate(expression: &str, context: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut parser = Parser { tokens: tokenize(expression)?, pos: 0 };
+    let expr = parser.conditional()?;
+
+    if parser.pos < parser.tokens.len() {
+        Err(format!("Unexpected {:?} in expression {}", parser.tokens[parser.pos], expression))?;
+    }
+
+    eval(&expr, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_precedence() {
+        let context = json!({"quantity": 3, "price": 2.5});
+        assert_eq!(evaluate("1 + 2 * 3", &context).unwrap(), json!(7));
+        assert_eq!(evaluate("(1 + 2) * 3", &context).unwrap(), json!(9));
+        assert_eq!(evaluate("-2 * 3 + 10 % 4", &context).unwrap(), json!(-4));
+        assert_eq!(evaluate("quantity * price", &context).unwrap(), json!(7.5));
+        assert_eq!(evaluate("1 + 1 == 2 && !(2 > 3) || false", &context).unwrap(), json!(true));
+        assert_eq!(evaluate("'total ' + quantity", &context).unwrap(), json!("total 3"));
+    }
+
+    #[test]
+    fn reads_number_literals() {
+        let context = json!({});
+        // integral literals compare equal to integer fields
+        assert_eq!(evaluate("2.0", &context).unwrap(), json!(2));
+        assert_eq!(evaluate("1e3", &context).unwrap(), json!(1000));
+        assert_eq!(evaluate("2.5E-1", &context).unwrap(), json!(0.25));
+        assert_eq!(evaluate(".5 + .5", &context).unwrap(), json!(1));
+    }
+
+    #[test]
+    fn evaluates_conditionals() {
+        let context = json!({"status": "open", "value": 0});
+        assert_eq!(evaluate("status == 'open' ? 1 : 2", &context).unwrap(), json!(1));
+        assert_eq!(evaluate("value ? 'yes' : value == 0 ? 'zero' : 'no'", &context).unwrap(), json!("zero"));
+        assert_eq!(evaluate("if(status = 'closed', 1, 2)", &context).unwrap(), json!(2));
+    }
+
+    #[test]
+    fn aggregates_child_rows() {
+        let context = json!({"items": [{"total": 1.5}, {"total": 2.5}, {"total": null}]});
+        assert_eq!(evaluate("sum(items.total)", &context).unwrap(), json!(4));
+        assert_eq!(evaluate("count(items)", &context).unwrap(), json!(3));
+        assert_eq!(evaluate("avg(items.total)", &context).unwrap(), json!(2));
+        assert_eq!(evaluate("max(items.total)", &context).unwrap(), json!(2.5));
+        assert_eq!(evaluate("round(sum(items.total) / 3, 2)", &context).unwrap(), json!(1.33));
+        assert_eq!(evaluate("sum(missing.total)", &context).unwrap(), json!(0));
+    }
+
+    #[test]
+    fn reports_errors() {
+        let context = json!({});
+        assert!(evaluate("1 +", &context).is_err());
+        assert!(evaluate("(1 + 2", &context).is_err());
+        assert!(evaluate("'open", &context).is_err());
+        assert!(evaluate("1 # 2", &context).is_err());
+        assert!(evaluate("1 2", &context).is_err());
+        assert!(evaluate("median(1)", &context).is_err());
+        assert!(evaluate("if(1, 2)", &context).is_err());
+        // division by zero has no value
+        assert_eq!(evaluate("1 / 0", &context).unwrap(), Value::Null);
+    }
+}
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+pub enum Locale {
+    #[serde(rename = "en")]
+    En,
+    #[default]
+    #[serde(rename = "pt-BR")]
+    PtBr,
+}
+
+// (key, en, pt-BR)
+const CATALOG: [(&str, &str, &str); 68] = [
+    ("apply", "Apply", "Aplicar"),
+    ("clear", "Clear", "Limpar"),
+    ("cancel", "Exit", "Sair"),
+    ("delete", "Remove", "Remover"),
+    ("clone", "Clone", "Clonar"),
+    ("delete_confirm", "Remove this record?", "Remover este registro?"),
+    ("delete_dependents", "Dependent rows removed with it", "Linhas dependentes removidas junto"),
+    ("trash", "Trash", "Lixeira"),
+    ("trash_exit", "Back to the list", "Voltar para a lista"),
+    ("restore", "Restore", "Restaurar"),
+    ("login_title", "Access control", "Controle de Acesso"),
+    ("login_user", "User", "Usuário"),
+    ("login_password", "Password", "Senha"),
+    ("login_locale", "Language", "Idioma"),
+    ("login_send", "Ok", "Ok"),
+    ("login_waiting", "Waiting for the server response...", "Aguardando resposta do servidor..."),
+    ("http_working", "Processing...", "Processando..."),
+    ("login_loading", "loading...", "carregando..."),
+    ("login_rows", "rows", "registros"),
+    ("filter", "Filter", "Filtro"),
+    ("aggregate", "Report", "Relatório"),
+    ("sort", "Sort", "Ordenamento"),
+    ("range_to", "to", "à"),
+    ("view", "View", "Visualizar"),
+    ("edit", "Edit", "Editar"),
+    ("new", "New", "Novo"),
+    ("search", "Search", "Pesquisa"),
+    ("back", "Back", "Voltar"),
+    ("forward", "Forward", "Avançar"),
+    ("select", "Select", "Selecionar"),
+    ("row_remove", "Delete", "Excluir"),
+    ("row_up", "Up", "Subir"),
+    ("row_down", "Down", "Descer"),
+    ("row_add", "Add row", "Adicionar linha"),
+    ("selected", "selected", "selecionados"),
+    ("select_all", "Select all matching", "Selecionar todos do filtro"),
+    ("select_none", "Clear selection", "Limpar seleção"),
+    ("bulk_remove", "Remove selected", "Remover selecionados"),
+    ("bulk_update", "Update selected", "Alterar selecionados"),
+    ("bulk_export", "Export selected", "Exportar selecionados"),
+    ("bulk_field", "Field", "Campo"),
+    ("bulk_value", "Value", "Valor"),
+    ("bulk_succeeded", "succeeded", "concluídos"),
+    ("bulk_failed", "failed", "com falha"),
+    ("page_size", "Page size", "Tamanho da página"),
+    ("download", "Download", "Baixar"),
+    ("hour_current", "current hour", "hora corrente"),
+    ("hour_previous", "previous hour", "hora anterior"),
+    ("hour_last", "last hour", "uma hora"),
+    ("day_current", "today", "dia corrente"),
+    ("day_previous", "yesterday", "dia anterior"),
+    ("day_last", "last day", "um dia"),
+    ("week_current", "current week", "semana corrente"),
+    ("week_previous", "previous week", "semana anterior"),
+    ("week_last", "last week", "uma semana"),
+    ("fortnight_current", "current fortnight", "quinzena corrente"),
+    ("fortnight_previous", "previous fortnight", "quinzena anterior"),
+    ("fortnight_last", "last fortnight", "uma quinzena"),
+    ("month_current", "current month", "mês corrente"),
+    ("month_previous", "previous month", "mês anterior"),
+    ("month_last", "last month", "um mês"),
+    ("year_current", "current year", "ano corrente"),
+    ("year_previous", "previous year", "ano anterior"),
+    ("year_last", "last year", "um ano"),
+    ("hour", "hour", "hora"),
+    ("day", "day", "dia"),
+    ("month", "month", "mês"),
+    ("year", "year", "ano"),
+];
+
+pub const FILTER_RANGES: [&str; 18] = [
+    "hour_current",
+    "hour_previous",
+    "hour_last",
+    "day_current",
+    "day_previous",
+    "day_last",
+    "week_current",
+    "week_previous",
+    "week_last",
+    "fortnight_current",
+    "fortnight_previous",
+    "fortnight_last",
+    "month_current",
+    "month_previous",
+    "month_last",
+    "year_current",
+    "year_previous",
+    "year_last",
+];
+
+pub const AGGREGATE_RANGES: [&str; 4] = ["hour", "day", "month", "year"];
+
+impl Locale {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::PtBr => "pt-BR",
+        }
+    }
+
+    // schema texts can be a plain string or an object by locale code, like {"en": "Name", "pt-BR": "Nome"}
+    pub fn localize(&self, value: &Value) -> Option<String> {
+        match value {
+            Value::String(str) => Some(str.clone()),
+            Value::Object(map) => map.get(self.code()).or_else(|| map.values().next()).and_then(|value| value.as_str()).map(|str| str.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn currency_symbol(&self) -> &'static str {
+        match self {
+            Locale::En => "$",
+            Locale::PtBr => "R$",
+        }
+    }
+
+    // (thousands, decimal)
+    pub fn number_separators(&self) -> (char, char) {
+        match self {
+            Locale::En => (',', '.'),
+            Locale::PtBr => ('.', ','),
+        }
+    }
+
+    // unknown keys are returned as is
+    pub fn translate(&self, key: &str) -> String {
+        match CATALOG.iter().find(|(item, _, _)| *item == key) {
+            Some((_, en, pt_br)) => match self {
+                Locale::En => en.to_string(),
+                Locale::PtBr => pt_br.to_string(),
+            },
+            None => key.to_string(),
+        }
+    }
+
+    // the whole catalog translated, for the texts of the page
+    pub fn labels(&self) -> Value {
+        let mut labels = serde_json::Map::new();
+
+        for (key, _, _) in CATALOG.iter() {
+            labels.insert(key.to_string(), Value::String(self.translate(key)));
+        }
+
+        Value::Object(labels)
+    }
+}
+
+// accepts the range key or its label in any locale, to keep old links and scripts working
+pub fn range_key(value: &str) -> Option<&'static str> {
+    let value = value.trim();
+
+    CATALOG
+        .iter()
+        .filter(|(key, _, _)| FILTER_RANGES.contains(key) || AGGREGATE_RANGES.contains(key))
+        .find(|(key, en, pt_br)| *key == value || *en == value || *pt_br == value)
+        .map(|(key, _, _)| *key)
+}
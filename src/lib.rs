@@ -1,8 +1,9 @@
 use anyhow::{anyhow, Context};
+use base64::Engine;
 use chrono::{DateTime, Datelike, Days, Local, Months, NaiveDateTime, TimeZone, Timelike, Utc};
 use convert_case::Casing;
 use indexmap::IndexMap;
-use openapiv3::{OpenAPI, ReferenceOr, Schema, SchemaKind, StringFormat, Type, VariantOrUnknownOrEmpty};
+use openapiv3::{ArrayType, OpenAPI, ReferenceOr, Schema, SchemaData, SchemaKind, StringFormat, Type, VariantOrUnknownOrEmpty};
 use regex;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
@@ -14,8 +15,52 @@ use rufs_base_rust::{
     rufs_micro_service::Role,
 };
 
+mod expression;
+pub mod i18n;
+mod mask;
+pub mod renderer;
+
+use i18n::Locale;
+use renderer::{ActionView, BootstrapRenderer, BulkView, CellView, ColumnView, DataViewRenderer, FieldView, FileView, FormView, NavigationItemView, NavigationView, PaginationView, ReferenceView, RowView, TableLabels, TableView};
+
+// log targets, so diagnostics can be filtered by area
+const LOG_HTTP: &str = "rufs_crud::http";
+const LOG_CACHE: &str = "rufs_crud::cache";
+const LOG_EVENT: &str = "rufs_crud::event";
+// references with more options than this are a type-ahead input instead of a select
+const TYPEAHEAD_MIN_OPTIONS: usize = 20;
+const TYPEAHEAD_LIMIT: usize = 20;
+// rows sent to the server by each step of a bulk operation, between the progress reports
+const BULK_BATCH: usize = 10;
+
+#[cfg(target_arch = "wasm32")]
+struct WebLogger;
+
+#[cfg(target_arch = "wasm32")]
+impl log::Log for WebLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            web_log::println!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(target_arch = "wasm32")]
+static WEB_LOGGER: WebLogger = WebLogger;
+
+// routes the log facade to the browser console, native applications install their own logger,
+// the logger is installed only once and the later calls just change the level
 #[cfg(target_arch = "wasm32")]
-use web_log::println;
+pub fn init_logger(level: log::LevelFilter) {
+    let _ = log::set_logger(&WEB_LOGGER);
+    log::set_max_level(level);
+}
 
 #[derive(Debug, PartialEq, Clone, Copy, Default, Deserialize, Serialize)]
 pub enum FieldSortType {
@@ -116,26 +161,44 @@ impl HttpRestRequest {
             client.request(method.clone(), &url)
         };
 
+        self.send(method, &url, request).await
+    }
+
+    // the raw content goes in the body, the server answers with the reference to keep in the field
+    async fn upload(&self, path: &str, data: Vec<u8>, content_type: &str) -> Result<Value, Box<dyn std::error::Error>> {
+        let url = format!("{}{}", self.url, path);
+        log::debug!(target: LOG_HTTP, "POST {} : uploading {} bytes ...", url, data.len());
+        let request = reqwest::Client::new().post(&url).header(reqwest::header::CONTENT_TYPE, content_type).body(data);
+        let data_in = self.send(Method::POST, &url, request).await?;
+        Ok(serde_json::from_str(&data_in).unwrap_or(Value::String(data_in)))
+    }
+
+    // authenticates, sends and logs the request, returning the body when the status is OK
+    async fn send(&self, method: Method, url: &str, request: reqwest::RequestBuilder) -> Result<String, Box<dyn std::error::Error>> {
         let request = if let Some(token) = &self.token { request.bearer_auth(token) } else { request };
 
-        println!("[HttpRestRequest::request_text] : waiting for {} {} ...", method, url);
+        log::debug!(target: LOG_HTTP, "{} {} ...", method, url);
+        let time_ini = Utc::now();
 
         let response = match request.send().await {
             Ok(response) => response,
             Err(err) => {
-                println!("[request_text] Error : {}", err);
+                log::error!(target: LOG_HTTP, "{} {} : {}", method, url, err);
                 return Err(Box::new(err) as Box<dyn std::error::Error>);
             }
         };
 
         let status = response.status();
         let data_in = response.text().await?;
-        println!("[HttpRestRequest::request_text] : ... returned {} from {}", status, url);
+        let elapsed = (Utc::now() - time_ini).num_milliseconds();
 
         if status != reqwest::StatusCode::OK {
+            log::warn!(target: LOG_HTTP, "{} {} : returned {} in {} ms", method, url, status, elapsed);
             return Err(data_in)?;
         }
 
+        log::info!(target: LOG_HTTP, "{} {} : returned {}, {} bytes in {} ms", method, url, status, data_in.len(), elapsed);
+
         Ok(data_in)
     }
 
@@ -160,10 +223,6 @@ impl HttpRestRequest {
         self.request(path, Method::PUT, params, item_send).await
     }
 
-    async fn query(&self, path: &str, params: &Value) -> Result<Value, Box<dyn std::error::Error>> {
-        self.request(path, Method::GET, params, &Value::Null).await
-    }
-
     async fn get(&self, path: &str, params: &Value) -> Result<Value, Box<dyn std::error::Error>> {
         let value = self.request(path, Method::GET, params, &Value::Null).await?;
 
@@ -235,22 +294,148 @@ pub struct Service {
     primary_keys: Vec<String>,
     list: Vec<Value>,
     list_str: Vec<String>,
+    // rows of other group owners, kept aside while switched to a tenant
+    list_hidden: Vec<Value>,
+    list_str_hidden: Vec<String>,
+    // column of x-softDelete, the rows where it is set are removed and kept aside for the trash view
+    soft_delete: Option<String>,
+    list_deleted: Vec<Value>,
+    list_str_deleted: Vec<String>,
+    // the whole list was read, an empty list can also be a service that isn't kept in cache
+    loaded: bool,
 }
 
 impl Service {
     pub fn new(openapi: &OpenAPI, path: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let (short_description_list, primary_keys, _) = openapi.get_properties_with_extensions(path, "get", &SchemaPlace::Response)?;
+        let schema_name = path[1..].to_string().to_case(convert_case::Case::Camel);
+
+        let soft_delete = match openapi.components.as_ref().and_then(|components| components.schemas.get(&schema_name)) {
+            Some(ReferenceOr::Item(schema)) => schema.schema_data.extensions.get("x-softDelete").and_then(|column| column.as_str()).map(|column| column.to_string()),
+            _ => None,
+        };
 
         Ok(Self {
             path: path.to_string(),
-            schema_name: path[1..].to_string().to_case(convert_case::Case::Camel),
+            schema_name,
             primary_keys,
             short_description_list,
             list: vec![],
             list_str: vec![],
+            list_hidden: vec![],
+            list_str_hidden: vec![],
+            soft_delete,
+            list_deleted: vec![],
+            list_str_deleted: vec![],
+            loaded: false,
         })
     }
 
+    fn set_list(&mut self, list: Vec<Value>, list_str: Vec<String>) {
+        self.list = list;
+        self.list_str = list_str;
+        self.list_deleted.clear();
+        self.list_str_deleted.clear();
+        self.filter_deleted();
+        self.loaded = true;
+    }
+
+    fn is_deleted(&self, item: &Value) -> bool {
+        match &self.soft_delete {
+            Some(column) => item.get(column).is_some_and(|value| !value.is_null()),
+            None => false,
+        }
+    }
+
+    // moves the rows between list and list_deleted following the x-softDelete column
+    fn filter_deleted(&mut self) {
+        if self.soft_delete.is_none() {
+            return;
+        }
+
+        let mut list = std::mem::take(&mut self.list);
+        let mut list_str = std::mem::take(&mut self.list_str);
+        list.append(&mut self.list_deleted);
+        list_str.append(&mut self.list_str_deleted);
+
+        for (item, str) in list.into_iter().zip(list_str.into_iter()) {
+            if self.is_deleted(&item) {
+                self.list_deleted.push(item);
+                self.list_str_deleted.push(str);
+            } else {
+                self.list.push(item);
+                self.list_str.push(str);
+            }
+        }
+    }
+
+    pub fn find_deleted_pos(&self, key: &Value) -> Option<usize> {
+        rufs_base_rust::data_store::Filter::find_index(&self.list_deleted, key).unwrap()
+    }
+
+    // takes the soft deleted row out of the trash, before it is sent again
+    fn take_deleted(&mut self, key: &Value) {
+        if let Some(pos) = self.find_deleted_pos(key) {
+            self.list_deleted.remove(pos);
+            self.list_str_deleted.remove(pos);
+        }
+    }
+
+    // group owner 1 (admin) see the rows of all tenants
+    fn is_visible(item: &Value, rufs_group_owner: u64) -> bool {
+        rufs_group_owner == 1
+            || match item.get("rufsGroupOwner").and_then(|value| value.as_u64()) {
+                Some(value) => value == rufs_group_owner,
+                None => true,
+            }
+    }
+
+    fn find_hidden_pos(&self, key: &Value) -> Option<usize> {
+        rufs_base_rust::data_store::Filter::find_index(&self.list_hidden, key).unwrap()
+    }
+
+    // takes the row of another tenant out of the hidden list, before it is written again
+    fn take_hidden(&mut self, key: &Value) {
+        if let Some(pos) = self.find_hidden_pos(key) {
+            self.list_hidden.remove(pos);
+
+            if pos < self.list_str_hidden.len() {
+                self.list_str_hidden.remove(pos);
+            }
+        }
+    }
+
+    // the row written at pos goes to the hidden list when it belongs to another tenant
+    fn hide_row(&mut self, pos: usize, rufs_group_owner: u64) {
+        if pos >= self.list.len() || pos >= self.list_str.len() || Service::is_visible(&self.list[pos], rufs_group_owner) {
+            return;
+        }
+
+        self.list_hidden.push(self.list.remove(pos));
+        self.list_str_hidden.push(self.list_str.remove(pos));
+    }
+
+    fn filter_group_owner(&mut self, rufs_group_owner: u64) {
+        let mut list = std::mem::take(&mut self.list);
+        let mut list_str = std::mem::take(&mut self.list_str);
+        list.append(&mut self.list_deleted);
+        list_str.append(&mut self.list_str_deleted);
+        list.append(&mut self.list_hidden);
+        list_str.append(&mut self.list_str_hidden);
+
+        for (item, str) in list.into_iter().zip(list_str.into_iter()) {
+            if Service::is_visible(&item, rufs_group_owner) {
+                self.list.push(item);
+                self.list_str.push(str);
+            } else {
+                self.list_hidden.push(item);
+                self.list_str_hidden.push(str);
+            }
+        }
+
+        self.filter_deleted();
+    }
+
     pub fn get_primary_key(&self, obj: &Value) -> Option<Value> {
         // private, projected for extract primaryKey and uniqueKeys
         fn copy_fields_from_list(data_in: &Value, field_names: &Vec<String>, retutn_null_if_any_empty: bool) -> Option<Value> {
@@ -272,13 +457,10 @@ impl Service {
         copy_fields_from_list(obj, &self.primary_keys, true)
     }
 
-    async fn query_remote(&self, server_connection: &ServerConnection, params: &Value) -> Result<(Vec<Value>, Vec<String>), Box<dyn std::error::Error>> {
-        let access = server_connection.login_response.roles.iter().find(|role| role.path == self.path).unwrap().mask;
-
-        if access & 1 != 0 {
-            //console.log("[ServerConnection] loading", service.label, "...");
-            //callback_partial("loading... " + service.label);
-            let value = server_connection.http_rest.query(&self.path, params).await?;
+    async fn query_remote(&self, server_connection: &ServerConnection, params: &Value) -> Result<(Vec<Value>, Vec<String>, usize), Box<dyn std::error::Error>> {
+        if server_connection.is_allowed(&self.path, "get") {
+            let data_in = server_connection.http_rest.request_text(&self.path, Method::GET, params, &Value::Null).await?;
+            let value = serde_json::from_str::<Value>(&data_in)?;
 
             let list = match value {
                 Value::Array(list) => list,
@@ -305,13 +487,13 @@ impl Service {
             */
 
             if list.len() != list_str.len() {
-                println!("[DEBUG - query_remote - {} - list.len({}) != list_str.len({})]", self.path, list.len(), list_str.len());
+                log::warn!(target: LOG_CACHE, "query_remote({}) : list.len({}) != list_str.len({})", self.path, list.len(), list_str.len());
             }
 
-            return Ok((list, list_str));
+            return Ok((list, list_str, data_in.len()));
         }
 
-        Ok((vec![], vec![]))
+        Ok((vec![], vec![], 0))
     }
 
     //find<'a>(list: &'a Vec<Value>, filter: &'a Value) -> Vec<&'a Value>
@@ -332,53 +514,36 @@ impl Service {
     }
     // private, use in get, save, update and remove
     pub fn update_list(&mut self, value: Value, pos: Option<usize>) -> usize {
-        #[cfg(debug_assertions)]
         if value.is_array() {
-            for _value in &self.list {
-                println!("[DEBUG - {:?} - {:?}]", self.get_primary_key(&value), value);
-            }
+            log::warn!(target: LOG_CACHE, "update_list({}) : unexpected array {}", self.path, value);
         }
 
         let ret = if let Some(pos) = pos {
+            log::debug!(target: LOG_CACHE, "update_list({}) : replace at {} : {:?}", self.path, pos, self.get_primary_key(&value));
             self.list[pos] = value;
-
-            if self.list.len() > self.list_str.len() + 1 {
-                println!("[DEBUG - update_list - {} - 1 - rufs_service.list.len({}) != rufs_service.list_str.len({})]", self.path, self.list.len(), self.list_str.len());
-            }
-
             pos
         } else {
             if let Some(key) = self.get_primary_key(&value) {
                 if let Some(pos) = self.find_pos(&key) {
+                    log::debug!(target: LOG_CACHE, "update_list({}) : replace at {} : {}", self.path, pos, key);
                     self.list[pos] = value;
-
-                    if self.list.len() > self.list_str.len() + 1 {
-                        println!("[DEBUG - update_list - {} - 2 - rufs_service.list.len({}) != rufs_service.list_str.len({})]", self.path, self.list.len(), self.list_str.len());
-                    }
-
                     pos
                 } else {
-                    #[cfg(debug_assertions)]
-                    if self.list.len() > self.list_str.len() {
-                        for _value in &self.list {
-                            println!("[DEBUG - {:?} - {:?}]", self.get_primary_key(&value), value);
-                        }
-                    }
-
+                    log::debug!(target: LOG_CACHE, "update_list({}) : insert at {} : {}", self.path, self.list.len(), key);
                     self.list.push(value);
                     self.list.len() - 1
                 }
             } else {
+                log::debug!(target: LOG_CACHE, "update_list({}) : insert at {} without primary key", self.path, self.list.len());
                 self.list.push(value);
-
-                if self.list.len() > self.list_str.len() + 1 {
-                    println!("[DEBUG - update_list - {} - 4 - rufs_service.list.len({}) != rufs_service.list_str.len({})]", self.path, self.list.len(), self.list_str.len());
-                }
-
                 self.list.len() - 1
             }
         };
 
+        if self.list.len() > self.list_str.len() + 1 {
+            log::warn!(target: LOG_CACHE, "update_list({}) : list.len({}) != list_str.len({})", self.path, self.list.len(), self.list_str.len());
+        }
+
         ret
     }
 
@@ -392,10 +557,15 @@ impl Service {
 
             let service = server_connection.service_map.get(&item.schema).context(format!("Don't found service {}", item.schema))?;
             let primary_key = item.primary_key;
-            let pos = service
-                .find_pos(&primary_key)
-                .context(format!("Don't found item {} in service {}.\ncandidates:{:?}\n", primary_key, item.schema, service.list))?;
-            let str = service.list_str[pos].clone();
+            // soft deleted rows keep their description for the rows that still reference them
+            let str = match service.find_pos(&primary_key) {
+                Some(pos) => service.list_str[pos].clone(),
+                None => service
+                    .find_deleted_pos(&primary_key)
+                    .map(|pos| service.list_str_deleted[pos].clone())
+                    .context(format!("Don't found item {} in service {}.\ncandidates:{:?}\n", primary_key, item.schema, service.list))?,
+            };
+
             Ok(str)
         }
 
@@ -445,6 +615,10 @@ impl Service {
                         return build_field_reference(server_connection, schema_name, field_name, obj, reference);
                     }
                 }
+
+                if let Some(str) = mask::format_value(extensions, value, &server_connection.locale) {
+                    return Ok(str);
+                }
             }
         }
 
@@ -491,9 +665,7 @@ impl Service {
             let str = self.build_item_str(server_connection, item)?;
 
             if let Some(pos) = list_out.iter().position(|s| s == &str) {
-                println!("already str in list, position {}", pos);
-                println!("item = {}", item);
-                println!("item[{}] = {}", pos, list[pos]);
+                log::error!(target: LOG_CACHE, "build_list_str({}) : already str in list, position {}, item = {}, item[{}] = {}", self.schema_name, pos, item, pos, list[pos]);
                 self.build_item_str(server_connection, item)?;
                 todo!()
             }
@@ -502,7 +674,7 @@ impl Service {
         }
 
         if self.list.len() != self.list_str.len() {
-            println!("[DEBUG - build_list_str - {} - rufs_service.list.len({}) != rufs_service.list_str.len({})]", self.schema_name, self.list.len(), self.list_str.len());
+            log::warn!(target: LOG_CACHE, "build_list_str({}) : list.len({}) != list_str.len({})", self.schema_name, self.list.len(), self.list_str.len());
         }
 
         Ok(list_out)
@@ -528,14 +700,37 @@ impl Service {
                 return Err(anyhow!(format!("[remove_internal({}, {})] index {} out of service.list_str.len {}", self.path, primary_key, index, self.list_str.len())))?;
             }
 
+            log::debug!(target: LOG_CACHE, "remove_internal({}) : remove at {} : {}", self.path, index, primary_key);
             self.list.remove(*index);
             self.list_str.remove(*index);
+        } else {
+            // the row can belong to a tenant hidden by switch_group_owner
+            self.take_hidden(primary_key);
         }
 
         Ok(index)
     }
 }
 
+#[derive(Serialize, Default, Debug)]
+pub struct DataViewSaveResult {
+    form_id: String,
+    index: Option<usize>,
+    primary_key: Option<Value>,
+    error: Option<String>,
+}
+
+impl DataViewSaveResult {
+    fn new(form_id: &str, index: Option<usize>, primary_key: Option<Value>, error: Option<String>) -> Self {
+        Self {
+            form_id: form_id.to_string(),
+            index,
+            primary_key,
+            error,
+        }
+    }
+}
+
 #[derive(Serialize, Default, Debug)]
 pub struct DataViewResponse {
     form_id: String,
@@ -543,12 +738,33 @@ pub struct DataViewResponse {
     changes: Value,
     tables: Value,
     aggregates: Value,
+    save_results: Vec<DataViewSaveResult>,
+    // html of the list editors of scalar arrays, by element id
+    lists: Value,
+    // html of the controls of cascading references, by element id
+    options: Value,
+    // ranked options of the type-ahead references, by element id
+    suggestions: Value,
+    // form_id of the forms to be removed from the page, like the list where a reference was selected
+    closed: Vec<String>,
+    // {hidden, disabled, required} of the fields with x-visibleIf, x-readOnlyIf or x-requiredIf, by element id
+    states: Value,
+    // {index, form_id, hash, html} of the current navigation entry, to sync the browser history and the breadcrumbs
+    navigation: Value,
+    // {form_id, operation, index, count, finished, summary} of the bulk operation, continued by bulk_continue-{form_id}
+    bulk: Value,
+    // {name, media_type, content} of a file to be saved by the browser
+    download: Value,
+    // {target, message} of an action that waits for the confirmation of the user
+    confirm: Value,
 }
 
 #[derive(PartialEq)]
 pub enum DataViewType {
     Primary,
     ObjectProperty,
+    // object property edited as a sub-form, its instance is kept in instance[schema_name] of the parent
+    ObjectNested,
     Dependent,
 }
 
@@ -697,6 +913,8 @@ pub struct DataView {
     typ: DataViewType,
     short_description_list: Vec<String>,
     extensions: IndexMap<String, Value>,
+    // title, description and extensions of the schema, or of the array property for ObjectProperty
+    schema_data: SchemaData,
     properties: IndexMap<String, ReferenceOr<Box<Schema>>>,
     properties_modified: IndexMap<String, Value>,
 
@@ -714,6 +932,16 @@ pub struct DataView {
     // data list
     active_index: Option<usize>, // active index of filter_results
     pub filter_results: Vec<Value>,
+    // after a filter, empty filter_results means no rows instead of all the rows of the service
+    filter_applied: bool,
+    // primary keys of the Dependent rows removed in the inline grid, deleted when the parent is saved
+    removed_rows: Vec<Value>,
+    // primary keys of the rows selected in the table, and the field and value of the bulk update
+    selected_rows: Vec<Value>,
+    bulk_field: Option<String>,
+    bulk_value: Value,
+    // the table lists the soft deleted rows of the service
+    trash: bool,
     field_filter_results: IndexMap<String, Value>,
     pub field_results: IndexMap<String, Vec<Value>>,
     field_results_str: IndexMap<String, Vec<String>>,
@@ -762,6 +990,7 @@ impl DataView {
             properties: IndexMap::default(),
             properties_modified: IndexMap::default(),
             extensions: IndexMap::default(),
+            schema_data: SchemaData::default(),
             field_filter_results: IndexMap::default(),
             field_results: IndexMap::default(),
             field_results_str: IndexMap::default(),
@@ -770,6 +999,12 @@ impl DataView {
             //list_str: vec![],
             active_index: None,
             filter_results: vec![],
+            filter_applied: false,
+            removed_rows: vec![],
+            selected_rows: vec![],
+            bulk_field: None,
+            bulk_value: Value::Null,
+            trash: false,
             current_page: 1,
             page_size: 25,
             active_primary_key: None,
@@ -804,12 +1039,21 @@ impl DataView {
         self.properties = properties;
         self.short_description_list = short_description_list;
 
+        if let Some(components) = &server_connection.login_response.openapi.components {
+            if let Some(ReferenceOr::Item(schema)) = components.schemas.get(&self.data_view_id.schema_name) {
+                self.schema_data = schema.schema_data.clone();
+            }
+        }
+
         if let Some(property) = self.properties.get_mut("rufsGroupOwner") {
             match property {
                 ReferenceOr::Item(property) => {
-                    property.schema_data.extensions.insert("x-hidden".to_string(), Value::Bool(true));
-                    property.schema_data.extensions.insert("x-tableVisible".to_string(), Value::Bool(false));
-                    property.schema_data.default = Some(Value::Number(Number::from(server_connection.login_response.rufs_group_owner)));
+                    if server_connection.login_response.rufs_group_owner != 1 {
+                        property.schema_data.extensions.insert("x-hidden".to_string(), Value::Bool(true));
+                        property.schema_data.extensions.insert("x-tableVisible".to_string(), Value::Bool(false));
+                    }
+
+                    property.schema_data.default = Some(Value::Number(Number::from(server_connection.rufs_group_owner)));
                 }
                 _ => todo!(),
             };
@@ -818,475 +1062,1121 @@ impl DataView {
         Ok(())
     }
 
-    pub fn clear(&mut self) {
-        self.original = json!({});
-        self.instance = json!({});
-        self.instance_flags.clear();
-        self.field_external_references_str.clear();
+    // x-label has precedence over title because it can be localized
+    fn label_from_schema_data(schema_data: &SchemaData, name: &str, locale: &Locale) -> String {
+        schema_data
+            .extensions
+            .get("x-label")
+            .and_then(|label| locale.localize(label))
+            .or_else(|| schema_data.title.clone())
+            .unwrap_or_else(|| name.to_case(convert_case::Case::Title))
     }
 
-    fn build_changes(&mut self, element_id: &HtmlElementId, data_out: &mut Value) -> Result<(), Box<dyn std::error::Error>> {
-        let mut form = json!({});
-
-        for (field_name, value) in &self.properties_modified {
-            form[field_name] = json!(value);
-        }
+    fn help_from_schema_data(schema_data: &SchemaData, locale: &Locale) -> Option<String> {
+        schema_data.extensions.get("x-description").and_then(|description| locale.localize(description)).or_else(|| schema_data.description.clone())
+    }
 
-        let form_id = format!("{}-{}", element_id.form_type, self.data_view_id.form_id);
-        data_out[form_id] = form;
-        self.properties_modified.clear();
+    fn expression_context(&self) -> Value {
+        let mut context = if self.instance.is_object() { self.instance.clone() } else { json!({}) };
 
-        for data_view in &mut self.childs {
-            data_view.build_changes(element_id, data_out)?;
+        // the rows of the child arrays and of the dependents, for expressions like sum(items.total)
+        for data_view in &self.childs {
+            if data_view.typ == DataViewType::ObjectProperty || data_view.typ == DataViewType::Dependent {
+                context[&data_view.data_view_id.schema_name] = json!(data_view.filter_results);
+            }
         }
 
-        Ok(())
+        context
     }
 
-    fn build_form(data_view_manager: &DataViewManager, data_view: &DataView, form_type: FormType) -> Result<String, Box<dyn std::error::Error>> {
-        let form_id = &data_view.data_view_id.form_id;
-        let form_type_str = match form_type {
-            FormType::Instance => "instance",
-            FormType::Filter => "filter",
-            FormType::Aggregate => "aggregate",
-            FormType::Sort => "sort",
-        };
+    // computed fields of x-expression whose value changed, evaluated until they settle, so they can depend on each other in any order
+    fn evaluate_expressions(&self) -> Vec<(String, Value)> {
+        let expressions = self
+            .properties
+            .iter()
+            .filter_map(|(field_name, field)| {
+                let expression = field.as_item()?.schema_data.extensions.get("x-expression")?.as_str()?;
+                Some((field_name.clone(), expression.to_string()))
+            })
+            .collect::<Vec<(String, String)>>();
 
-        let mut hmtl_fields = vec![];
+        if expressions.is_empty() {
+            return vec![];
+        }
 
-        for (field_name, field) in &data_view.properties {
-            let field = field.as_item().context("field is reference")?;
-            let extension = &field.schema_data.extensions;
-            let hidden = extension.get("x-hidden").unwrap_or(&Value::Bool(false)).as_bool().unwrap_or(false);
+        let mut changes = IndexMap::<String, Value>::new();
+        let mut context = self.expression_context();
 
-            if hidden {
-                continue;
-            }
+        for _ in 0..expressions.len() {
+            let mut changed = false;
 
-            let typ = match &field.schema_kind {
-                SchemaKind::Type(typ) => typ,
-                SchemaKind::Any(_) => todo!(),
-                _ => continue,
-            };
+            for (field_name, expression) in &expressions {
+                // failures clear the field, instead of keeping a value computed from older inputs
+                let value = match expression::evaluate(expression, &context) {
+                    Ok(value) => value,
+                    Err(err) => {
+                        log::warn!(target: LOG_EVENT, "evaluate_expressions({}.{}) : {}", self.data_view_id.form_id, field_name, err);
+                        Value::Null
+                    }
+                };
 
-            let (html_input_typ, html_input_step, html_input_pattern, html_input_max_length, col_size, is_rangeable) = match typ {
-                Type::String(typ) => {
-                    let max_length = typ.max_length.unwrap_or(1024);
+                if expression::values_equal(&value, context.get(field_name).unwrap_or(&Value::Null)) {
+                    continue;
+                }
 
-                    let col_size = if max_length > 110 { 11 } else { (max_length / 10) + 1 };
+                context[field_name] = value.clone();
+                changes.insert(field_name.clone(), value);
+                changed = true;
+            }
 
-                    let (html_input_typ, is_rangeable) = match &typ.format {
-                        VariantOrUnknownOrEmpty::Item(format) => match format {
-                            StringFormat::Date => ("date", true),
-                            StringFormat::DateTime => ("datetime-local", true),
-                            StringFormat::Password => ("text", false),
-                            StringFormat::Byte => ("text", false),
-                            StringFormat::Binary => ("text", false),
-                        },
-                        _ => ("text", false),
-                    };
+            if !changed {
+                break;
+            }
+        }
 
-                    (html_input_typ, "".to_string(), "", max_length, col_size, is_rangeable)
-                }
-                Type::Number(_typ) => {
-                    let precision: usize = extension.get("x-precision").unwrap_or(&json!(12)).as_u64().unwrap_or(12).try_into().unwrap_or(12);
-                    let scale = if let Some(scale) = extension.get("x-scale") {
-                        match scale.as_u64().unwrap_or(3) {
-                            1 => "0.1",
-                            3 => "0.001",
-                            4 => "0.0001",
-                            5 => "0.00001",
-                            _ => "0.01",
-                        }
-                    } else {
-                        "0.01"
-                    };
+        changes.into_iter().collect()
+    }
 
-                    ("number", format!(r#"step="{}""#, scale), "", precision, 2, true)
-                }
-                Type::Integer(_typ) => {
-                    if let Some(_reference) = extension.get("x-$ref") {
-                        ("text", "".to_string(), "", 1024, 8, false)
-                    } else {
-                        ("number", r#"step="1""#.to_string(), r#"pattern="\d+""#, 15, 2, true)
-                    }
+    // x-visibleIf, x-readOnlyIf and x-requiredIf are a boolean or an expression over the instance
+    fn evaluate_rule(&self, context: &Value, field_name: &str, extensions: &IndexMap<String, Value>, rule: &str) -> Option<bool> {
+        match extensions.get(rule)? {
+            Value::Bool(value) => Some(*value),
+            Value::String(expression) => match expression::evaluate(expression, context) {
+                Ok(value) => Some(expression::is_true(&value)),
+                Err(err) => {
+                    log::warn!(target: LOG_EVENT, "evaluate_rule({}.{} {}) : {}", self.data_view_id.form_id, field_name, rule, err);
+                    None
                 }
-                Type::Boolean {} => ("checkbox", "".to_string(), "", 0, 1, false),
-                Type::Object(_) => continue,
-                Type::Array(_) => continue,
-            };
+            },
+            _ => None,
+        }
+    }
 
-            let mut html_options = vec![];
+    // (hidden, disabled, required) of the fields that have rules
+    fn evaluate_field_states(&self) -> IndexMap<String, (bool, bool, bool)> {
+        let context = self.expression_context();
+        let mut states = IndexMap::new();
 
-            let html_input = match typ {
-                Type::Object(_) => {
-                    format!(
-                        r##"
+        for (field_name, field) in &self.properties {
+            let Some(field) = field.as_item() else {
+                continue;
+            };
 
-                    "##
-                    )
-                }
-                Type::Array(_) => {
-                    format!(
-                        r##"
-                    
-                    "##
-                    )
-                }
-                _ => {
-                    if data_view.action != DataViewProcessAction::View {
-                        if let Some(list) = data_view.field_results_str.get(field_name) {
-                            for str in list {
-                                html_options.push(format!(r##"<option value="{str}">{str}</option>"##));
-                            }
-                        }
-                    }
+            let extensions = &field.schema_data.extensions;
 
-                    let html_options_str = html_options.join("\n");
+            if !["x-visibleIf", "x-readOnlyIf", "x-requiredIf"].iter().any(|rule| extensions.contains_key(*rule)) {
+                continue;
+            }
 
-                    if data_view.action != DataViewProcessAction::View && html_options.len() > 0 && html_options.len() <= 20 {
-                        format!(
-                            r##"
-                        <select class="form-control" id="{form_type_str}-{form_id}-{field_name}" name="{field_name}" ng-required="field.essential == true && field.nullable != true" ng-disabled="{{field.readOnly == true}}">
-                            <option value=""></option>
-                            {html_options_str}
-                        </select>
-                        "##
-                        )
-                    } else {
-                        // ng-disabled="{{field.readOnly == true}}"
-                        let disabled = if data_view.action == DataViewProcessAction::View { "disabled" } else { "" };
+            let essential = extensions.get("x-essential").and_then(|value| value.as_bool()).unwrap_or(false);
+            let hidden = self.evaluate_rule(&context, field_name, extensions, "x-visibleIf").map(|visible| !visible).unwrap_or(false);
+            let disabled = self.evaluate_rule(&context, field_name, extensions, "x-readOnlyIf").unwrap_or(false);
+            let required = self.evaluate_rule(&context, field_name, extensions, "x-requiredIf").unwrap_or(essential && !field.schema_data.nullable);
+            states.insert(field_name.clone(), (hidden, disabled, required));
+        }
 
-                        format!(
-                            r##"
-                        <input class="form-control" id="{form_type_str}-{form_id}-{field_name}" name="{field_name}" type="{html_input_typ}" {html_input_step} {html_input_pattern} maxlength="{html_input_max_length}" placeholder="" ng-required="field.essential == true && field.nullable != true" {disabled} list="list-{form_id}-{field_name}" autocomplete="off">
-                        <datalist ng-if="field.filterResultsStr.length >  20" id="list-{form_id}-{field_name}">
-                            {html_options_str}
-                        </datalist>
-                        "##
-                        )
-                    }
-                }
-            };
+        states
+    }
 
-            let (html_external_search, html_references) = if let Some(_reference) = extension.get("x-$ref") {
-                //let reference = reference.as_str().context("not string content")?;
-                let mut list = vec![];
-                list.push(format!(
-                    r##"<div class="col-1"><a id="reference-view-{form_id}-{field_name}" name="reference-view-{field_name}" class="btn btn-secondary" href><i class="bi bi-eye-open"></i></a></div>"##
-                ));
-
-                let html_external_search = if data_view.action != DataViewProcessAction::View {
-                    list.push(format!(r##"<div class="col-1"><a id="reference-create-{form_id}-{field_name}" name="reference-create-{field_name}" class="btn btn-secondary" href><i class="bi bi-plus"></i></a></div>"##));
-                    let html_external_search = format!(
-                        r##"<div class="col-1"><a id="reference-search-{form_id}-{field_name}" name="reference-search-{field_name}" class="btn btn-secondary" href><i class="bi bi-search"></i></a></div>"##
-                    );
-                    list.push(html_external_search.clone());
-                    html_external_search
-                } else {
-                    "".to_string()
-                };
+    // all the rules are sent in every response, so the form always matches the instance, even after being rebuilt
+    fn build_states(&self, data_out: &mut Value) {
+        for (field_name, (hidden, disabled, required)) in self.evaluate_field_states() {
+            let id = format!("instance-{}-{}", self.data_view_id.form_id, field_name);
+            // the same rule of build_form_view, so a computed field is never enabled by its states
+            let disabled = disabled || self.action == DataViewProcessAction::View || self.is_computed(&field_name);
+            data_out[id] = json!({"hidden": hidden, "disabled": disabled, "required": required && !hidden});
+        }
 
-                (html_external_search, list.join("\n"))
-            } else {
-                ("".to_string(), "".to_string())
-            };
+        for data_view in &self.childs {
+            data_view.build_states(data_out);
+        }
+    }
 
-            let html_flags = if let Some(flags) = extension.get("x-flags") {
-                let flags = flags.as_array().context(format!("Not array content in extension 'x-flags' of field {}, content : {}", field_name, flags))?;
-                let mut list = vec![];
-                let mut index = 0;
+    // the hidden fields are never required
+    fn check_field_states(&self) -> Result<(), Box<dyn std::error::Error>> {
+        for (field_name, (hidden, _, required)) in self.evaluate_field_states() {
+            let value = self.instance.get(&field_name).unwrap_or(&Value::Null);
 
-                for label in flags {
-                    let label = label.as_str().context("not string content")?;
-
-                    list.push(format!(
-                        r##"
-                    <div class="form-group form-group row">
-                        <label class="col-offset-1 control-label">
-                            <input type="checkbox" id="{form_type_str}-{form_id}-{field_name}-{index}" name="{field_name}-{index}"/>
-                            {label}
-                        </label>
-                    </div>
-                    "##
-                    ));
-                    index += 1;
-                }
-
-                list.join("\n")
-            } else {
-                "".to_string()
-            };
+            if required && !hidden && (value.is_null() || value.as_str() == Some("")) {
+                Err(format!("Field {} of {} is required", field_name, self.data_view_id.form_id))?;
+            }
+        }
 
-            let label = field_name.to_case(convert_case::Case::Title);
-            let str = match form_type {
-                FormType::Instance => {
-                    format!(
-                        r##"
-                        <div class="col-{col_size}">
-                            <label for="{form_type_str}-{form_id}-{field_name}" class="control-label">{label}</label>
-                            <div class="row">
-                                <div class="col">{html_input}</div>
-                                {html_references}
-                                {html_flags}
-                            </div>
-                        </div>
-                        "##
-                    )
-                }
-                FormType::Filter => {
-                    let html_field_range = if ["date", "datetime-local"].contains(&html_input_typ) {
-                        let filter_range_options = [
-                            " hora corrente ",
-                            " hora anterior ",
-                            " uma hora ",
-                            " dia corrente ",
-                            " dia anterior ",
-                            " um dia ",
-                            " semana corrente ",
-                            " semana anterior ",
-                            " uma semana ",
-                            " quinzena corrente ",
-                            " quinzena anterior ",
-                            " uma quinzena ",
-                            " mês corrente ",
-                            " mês anterior ",
-                            " um mês ",
-                            " ano corrente ",
-                            " ano anterior ",
-                            " um ano ",
-                        ];
-                        let mut html_options = vec![];
-
-                        for option in filter_range_options {
-                            html_options.push(format!(r##"<option value="{option}">{option}</option>"##));
-                        }
+        for data_view in &self.childs {
+            if data_view.typ == DataViewType::ObjectNested {
+                data_view.check_field_states()?;
+            }
+        }
 
-                        let html_options = html_options.join("\n");
-                        format!(
-                            r#"
-                        <div class="form-group">
-                            <div ng-if="field.htmlType.includes('date')" class="col-offset-3 col-9">
-                                <select class="form-control" id="{form_type_str}-{form_id}-{field_name}-range" name="{field_name}-range" ng-model="vm.instanceFilterRange[fieldName]" ng-change="vm.setFilterRange(fieldName, vm.instanceFilterRange[fieldName])">
-                                    <option value=""></option>
-                                    {html_options}
-                                </select>
-                            </div>
-                        </div>	    
-                        "#
-                        )
-                    } else {
-                        "".to_string()
-                    };
+        Ok(())
+    }
 
-                    let html_input = if html_options.len() > 0 {
-                        format!(r#"<div class="col">{html_input}</div>"#)
-                    } else {
-                        match typ {
-                            Type::Object(_) => "".to_string(),
-                            Type::Array(_) => "".to_string(),
-                            _ => {
-                                if is_rangeable {
-                                    format!(
-                                        r#"
-                                    <div class="col-4">
-                                        <input class="form-control" id="{form_type_str}-{form_id}-{field_name}@min" name="{field_name}@min" type="{html_input_typ}" {html_input_step} placeholder="">
-                                    </div>
-                            
-                                    <label for="{field_name}@max" class="col-1 control-label" style="text-align: center">à</label>
-                            
-                                    <div class="col-4">
-                                        <input class="form-control" id="{form_type_str}-{form_id}-{field_name}@max" name="{field_name}@max" type="{html_input_typ}" {html_input_step} placeholder="">
-                                    </div>
-                                    "#
-                                    )
-                                } else {
-                                    format!(
-                                        r#"
-                                    <div class="col-9">
-                                        <input class="form-control" id="{form_type_str}-{form_id}-{field_name}" name="{field_name}" type="{html_input_typ}" {html_input_step} placeholder="">
-                                    </div>
-                                    "#
-                                    )
-                                }
-                            }
-                        }
-                    };
+    fn is_computed(&self, field_name: &str) -> bool {
+        self.properties
+            .get(field_name)
+            .and_then(|field| field.as_item())
+            .map(|field| field.schema_data.extensions.contains_key("x-expression"))
+            .unwrap_or(false)
+    }
 
-                    format!(
-                        r#"
-                        {html_field_range}
-                        <div class="form-group row">
-                            <label for="{form_type_str}-{form_id}-{field_name}" class="control-label col-2">{label}</label>
-                            {html_input}
-                            {html_external_search}
-                        </div>
-                    "#
-                    )
-                }
-                FormType::Aggregate => {
-                    let html_input = if ["date", "datetime-local"].contains(&html_input_typ) {
-                        let mut html_options = vec![];
+    // arrays of scalars are edited as a list of inputs in the parent form, instead of a child data view
+    fn list_item_schema(field: &Schema) -> Option<(&ArrayType, &Schema)> {
+        let SchemaKind::Type(Type::Array(array)) = &field.schema_kind else {
+            return None;
+        };
 
-                        for aggregate_range_option in ["", "hora", "dia", "mês", "ano"] {
-                            html_options.push(format!(r##"<option value="{aggregate_range_option}">{aggregate_range_option}</option>"##));
-                        }
+        let item = array.items.as_ref()?.as_item()?;
 
-                        let html_options = html_options.join("\n");
-                        format!(
-                            r#"
-                        <div class="col-9">
-                            <select class="form-control" id="{form_type_str}-{form_id}-{field_name}" name="{field_name}">
-                                <option value=""></option>
-                                {html_options}
-                            </select>
-                        </div>
-                        "#
-                        )
-                    } else {
-                        let html_input = if is_rangeable {
-                            format!(r#"<input  class="form-control" id="{form_type_str}-{form_id}-{field_name}" name="{field_name}" type="{html_input_typ}" {html_input_step} placeholder="">"#)
-                        } else {
-                            format!(r#"<input  class="form-control" id="{form_type_str}-{form_id}-{field_name}" name="{field_name}" type="checkbox">"#)
-                        };
+        match &item.schema_kind {
+            SchemaKind::Type(Type::Object(_)) | SchemaKind::Type(Type::Array(_)) => None,
+            SchemaKind::Type(_) => Some((array, item.as_ref())),
+            _ => None,
+        }
+    }
 
-                        format!(r#"<div class="col-4">{html_input}</div>"#)
-                    };
+    fn file_encoding(field: &Schema) -> Option<&'static str> {
+        match &field.schema_kind {
+            SchemaKind::Type(Type::String(typ)) => match &typ.format {
+                VariantOrUnknownOrEmpty::Item(StringFormat::Byte) => Some("byte"),
+                VariantOrUnknownOrEmpty::Item(StringFormat::Binary) => Some("binary"),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 
-                    format!(r#"<div class="form-group row"><label for="{form_type_str}-{form_id}-{field_name}" class="control-label">{label}</label>{html_input}</div>"#)
-                }
-                FormType::Sort => {
-                    format!(
-                        r#"
-                        <div class="form-group row">
-                            <label for="{form_type_str}-{form_id}-{field_name}" class="control-label">{label}</label>
-                                
-                            <div class="col-3">
-                                <select class="form-control" id="{form_type_str}-{form_id}-{field_name}-order_by" name="{field_name}-order_by" ng-model="vm.properties[fieldName].sortType">
-                                    <option value="asc">asc</option>
-                                    <option value="desc">desc</option>
-                                </select>
-                            </div>
-                    
-                            <div class="col-3">
-                                <input  class="form-control" id="{form_type_str}-{form_id}-{field_name}-index" name="{field_name}-index" ng-model="vm.properties[fieldName].orderIndex" type="number" step="1">
-                            </div>
-                    
-                            <div class="col-3">
-                                <input  class="form-control" id="{form_type_str}-{form_id}-{field_name}-table_visible" name="{field_name}-table_visible" type="checkbox">
-                            </div>
-                        </div>
-                    "#
-                    )
-                }
-            };
-
-            hmtl_fields.push(str);
-        }
-
-        let html_fields = hmtl_fields.join("\n");
-        let mut crud_item_json = vec![];
-
-        let (form_class, hidden_form, header, search, table) = match form_type {
-            FormType::Instance => {
-                for data_view in &data_view.childs {
-                    let form_instance = DataView::build_form(data_view_manager, data_view, FormType::Instance)?;
-                    crud_item_json.push(form_instance);
-                }
+    fn file_media_type(field: &Schema) -> Option<String> {
+        let extensions = &field.schema_data.extensions;
+        extensions.get("contentMediaType").or(extensions.get("x-contentMediaType")).and_then(|value| value.as_str()).map(|value| value.to_string())
+    }
 
-                let label = data_view.data_view_id.schema_name.to_case(convert_case::Case::Title);
-                let href_new = DataView::build_location_hash(&data_view.data_view_id.form_id, "new", &json!({}))?;
+    // byte values are shown as data url, binary values are the link of the uploaded file or the data url of the file not yet uploaded
+    fn file_href(field: &Schema, value: &Value) -> Option<String> {
+        let value = value.as_str().filter(|value| value.len() > 0)?;
 
-                let header = format!(
-                    r#"
-                    <div class="card-header">
-                        <a href="{href_new}" id="create-{form_type_str}-{form_id}" class="btn btn-default"><i class="bi bi-plus"></i> {label}</a>
-                    </div>
-                "#
-                );
-                let html_filter = DataView::build_form(data_view_manager, data_view, FormType::Filter)?;
-                let html_aggregate = DataView::build_form(data_view_manager, data_view, FormType::Aggregate)?;
-                let html_sort = DataView::build_form(data_view_manager, data_view, FormType::Sort)?;
-                let search = format!(
-                    r##"
-                    <div class="panel panel-default" ng-if="vm.rufsService.list.length > 0 || vm.rufsService.access.get == true">
-                        <nav>
-                            <div class="nav nav-tabs" role="tablist" id="nav-tab-{form_id}">
-                                <button class="nav-link" data-bs-toggle="tab" data-bs-target="#nav-filter-{form_id}"      role="tab" type="button" aria-controls="nav-filter-{form_id}"      aria-selected="false" id="nav-tab-filter-{form_id}">Filtro</button>
-                                <button class="nav-link" data-bs-toggle="tab" data-bs-target="#nav-aggregate-{form_id}"   role="tab" type="button" aria-controls="nav-aggregate-{form_id}"   aria-selected="false" id="nav-tab-aggregate-{form_id}">Relatório</button>
-                                <button class="nav-link" data-bs-toggle="tab" data-bs-target="#nav-sort-{form_id}"        role="tab" type="button" aria-controls="nav-sort-{form_id}"        aria-selected="false" id="nav-tab-sort-{form_id}">Ordenamento</button>
-                            </div>
-                        </nav>
-                    
-                        <div class="tab-content">
-                            <div class="tab-pane fade" id="nav-filter-{form_id}" role="tabpanel" aria-labelledby="nav-tab-filter-{form_id}" tabindex="0">
-                            {html_filter}
-                            </div>
-                        
-                            <div class="tab-pane fade" id="nav-aggregate-{form_id}" role="tabpanel" aria-labelledby="nav-tab-aggregate-{form_id}" tabindex="0">
-                            <canvas id="chart-aggregate-{form_id}"></canvas>
-                            {html_aggregate}
-                            </div>
-                        
-                            <div class="tab-pane fade" id="nav-sort-{form_id}" role="tabpanel" aria-labelledby="nav-tab-sort-{form_id}" tabindex="0">
-                            {html_sort}
-                            </div>
-                        </div>
-                    </div>
-                "##
-                );
-                let table = format!(
-                    r#"
-                    <div id="div-table-{form_id}" class="table-responsive" style="white-space: nowrap;">
-                    </div>
-                "#
-                );
-/*
-                let hidden = if data_view.data_view_id.parent_name.is_none() {
-                    "hidden"
-                } else {
-                    ""
-                };
-*/
-                ("row", "hidden", header, search, table)
+        match DataView::file_encoding(field)? {
+            "byte" => {
+                let media_type = DataView::file_media_type(field).filter(|media_type| !media_type.contains('*')).unwrap_or("application/octet-stream".to_string());
+                Some(format!("data:{};base64,{}", media_type, value))
             }
-            _ => ("form-horizontal", "", "".to_string(), "".to_string(), "".to_string()),
-        };
+            _ => Some(value.to_string()),
+        }
+    }
 
-        let html_crud_items = crud_item_json.join("\n");
+    fn list_item_default(item_schema: &Schema) -> Value {
+        if let Some(default) = &item_schema.schema_data.default {
+            return default.clone();
+        }
+
+        match &item_schema.schema_kind {
+            SchemaKind::Type(Type::Number(_)) | SchemaKind::Type(Type::Integer(_)) => json!(0),
+            SchemaKind::Type(Type::Boolean {}) => json!(false),
+            _ => json!(""),
+        }
+    }
+
+    fn parse_list_item(server_connection: &ServerConnection, field_name: &str, item_schema: &Schema, value: &str) -> Result<Value, Box<dyn std::error::Error>> {
+        let value = server_connection.login_response.openapi.copy_value_field(item_schema, true, &json!(value))?;
+
+        if let Some(reason) = DataView::check_cell(item_schema, &value) {
+            Err(format!("Item of {} {}", field_name, reason))?;
+        }
+
+        Ok(value)
+    }
+
+    // "*name" in the query of x-$ref takes the value of the field name of this instance,
+    // like "#/components/schemas/city?state=*state", giving the (foreign field, field) pairs
+    fn reference_cascade(field: &Schema) -> Vec<(String, String)> {
+        let Some(reference) = field.schema_data.extensions.get("x-$ref").and_then(|reference| reference.as_str()) else {
+            return vec![];
+        };
+
+        let Some(pos) = reference.chars().position(|c| c == '?') else {
+            return vec![];
+        };
+
+        let Ok(query) = queryst::parse(&reference[pos..]) else {
+            return vec![];
+        };
+
+        let mut list = vec![];
+
+        for (foreign_name, value) in query.as_object().into_iter().flatten() {
+            if let Some(name) = value.as_str().and_then(|value| value.strip_prefix('*')) {
+                list.push((foreign_name.clone(), name.to_string()));
+            }
+        }
+
+        list
+    }
+
+    // the fields whose options are narrowed by the value of field_name
+    fn field_filter_dependents(&self, field_name: &str) -> Vec<String> {
+        self.properties
+            .iter()
+            .filter_map(|(name, field)| field.as_item().map(|field| (name, field)))
+            .filter(|(_, field)| DataView::reference_cascade(field).iter().any(|(_, name)| name == field_name))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    fn build_field_filter_result(&self, server_connection: &ServerConnection, field_name: &str) -> Result<(Vec<Value>, Vec<String>), Box<dyn std::error::Error>> {
+        let field = self.properties.get(field_name).context(format!("Missing field {}", field_name))?.as_item().context("field is reference")?;
+        let extensions = &field.schema_data.extensions;
+
+        let result = if let Some(reference) = extensions.get("x-$ref") {
+            let reference = reference.as_str().context("reference is not string")?;
+
+            let service = server_connection.service_map.get(&self.data_view_id.schema_name).context(format!(
+                "[build_field_filter_results] Missing service {} in server_connection.service_map.",
+                self.data_view_id.schema_name
+            ))?;
 
-        let hidden = if data_view_manager.data_view_map.contains_key(&data_view.data_view_id.form_id) {
-            ""
+            if let Some(service) = server_connection.get_foreign_service(service, field_name, true) {
+                let mut filter = if let Some(filter) = self.field_filter_results.get(field_name) {
+                    filter.clone()
+                } else {
+                    json!({})
+                };
+
+                // without value in the driving field the options aren't narrowed
+                if filter.as_object().context("filter is not object")?.is_empty() {
+                    for (foreign_name, name) in DataView::reference_cascade(field) {
+                        let value = self.instance.get(&name).unwrap_or(&Value::Null);
+
+                        if !value.is_null() {
+                            filter[foreign_name] = value.clone();
+                        }
+                    }
+                }
+
+                let filter = filter.as_object().context("filter is not object")?;
+
+                if filter.is_empty() == false {
+                    let mut list = vec![];
+                    let mut list_str = vec![];
+
+                    for (index, candidate) in service.list.iter().enumerate() {
+                        if filter.iter().all(|(name, value)| candidate.get(name) == Some(value)) {
+                            list.push(candidate.clone());
+                            list_str.push(service.list_str.get(index).cloned().unwrap_or_default());
+                        }
+                    }
+
+                    (list, list_str)
+                } else {
+                    (service.list.clone(), service.list_str.clone())
+                }
+            } else {
+                log::debug!(target: LOG_CACHE, "build_field_filter_results : don't have acess to service {}", reference);
+                (vec![], vec![])
+            }
+        } else if let Some(enumeration) = extensions.get("x-enum") {
+            let enumeration = enumeration.as_array().context("x-enum is not array")?;
+
+            let list_str = if let Some(enum_labels) = extensions.get("x-enumLabels") {
+                enum_labels.as_array().unwrap().iter().map(|s| s.as_str().unwrap().to_string()).collect()
+            } else {
+                enumeration.iter().map(|s| s.to_string()).collect()
+            };
+
+            (enumeration.clone(), list_str)
         } else {
-            "hidden"
+            (vec![], vec![])
         };
 
-        let str = format!(
-            r##"
-            <div id="div-{form_type_str}-{form_id}" class="card" {hidden}>
-                {header}
-                <div class="card-body">
-                    <form id="{form_type_str}-{form_id}" name="{form_type_str}-{form_id}" class="{form_class}" role="form" {hidden_form}>
-                        {html_fields}
-                        <div class="form-group">
-                            <button id="apply-{form_type_str}-{form_id}"  name="apply"  class="btn btn-primary"><i class="bi bi-apply"></i> Aplicar</button>
-                            <button id="clear-{form_type_str}-{form_id}"  name="clear"  class="btn btn-default"><i class="bi bi-erase"></i> Limpar</button>
-                            <button id="cancel-{form_type_str}-{form_id}" name="cancel" class="btn btn-default"><i class="bi bi-exit"></i> Sair</button>
-                            <button id="delete-{form_type_str}-{form_id}" name="delete" class="btn btn-default"><i class="bi bi-remove"></i> Remove</button>
-                        </div>
-                    </form>
-                    {html_crud_items}
-                    {search}
-                    {table}
-                </div>
-            </div>
-        "##
-        );
+        Ok(result)
+    }
 
-        Ok(str)
+    // faz uma referencia local a field.filter_results_str, para permitir opção filtrada, sem alterar a referencia global
+    fn build_field_filter_results(&mut self, server_connection: &ServerConnection) -> Result<(), Box<dyn std::error::Error>> {
+        let field_names = self.properties.keys().cloned().collect::<Vec<String>>();
+        self.build_field_filter_results_of(server_connection, field_names)
+    }
+
+    // the fields whose options depend on the instance
+    fn cascading_fields(&self) -> Vec<String> {
+        self.properties
+            .iter()
+            .filter(|(_, field)| field.as_item().is_some_and(|field| DataView::reference_cascade(field).len() > 0))
+            .map(|(field_name, _)| field_name.clone())
+            .collect()
+    }
+
+    fn build_field_filter_results_of(&mut self, server_connection: &ServerConnection, field_names: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+        for field_name in field_names {
+            let (list, list_str) = self.build_field_filter_result(server_connection, &field_name)?;
+            self.field_results.insert(field_name.clone(), list);
+            self.field_results_str.insert(field_name, list_str);
+        }
+
+        Ok(())
+    }
+
+    fn build_options(data_view_manager: &DataViewManager, data_view: &DataView) -> Result<Value, Box<dyn std::error::Error>> {
+        let cascading = data_view.cascading_fields();
+
+        let mut options = json!({});
+
+        if cascading.is_empty() {
+            return Ok(options);
+        }
+
+        let form = DataView::build_form_view(data_view_manager, data_view, FormType::Instance)?;
+
+        for field in form.fields.iter().filter(|field| cascading.contains(&field.name)) {
+            options[&field.id] = json!(data_view_manager.renderer.render_field_control(field));
+        }
+
+        Ok(options)
+    }
+
+    // exact match first, then prefix, prefix of a word and substring, ignoring case, the shortest first in each rank
+    fn rank_options(list_str: &Vec<String>, text: &str, limit: usize) -> Vec<String> {
+        let text = text.trim().to_lowercase();
+
+        if text.is_empty() {
+            return list_str.iter().take(limit).cloned().collect();
+        }
+
+        let mut ranked = vec![];
+
+        for str in list_str {
+            let lower = str.to_lowercase();
+
+            let rank = if lower == text {
+                0
+            } else if lower.starts_with(&text) {
+                1
+            } else if lower.split(|c: char| !c.is_alphanumeric()).any(|word| word.starts_with(&text)) {
+                2
+            } else if lower.contains(&text) {
+                3
+            } else {
+                continue;
+            };
+
+            ranked.push((rank, str.chars().count(), str));
+        }
+
+        ranked.sort();
+        ranked.into_iter().take(limit).map(|(_, _, str)| str.clone()).collect()
+    }
+
+    fn build_lists(data_view_manager: &DataViewManager, data_view: &DataView) -> Result<Value, Box<dyn std::error::Error>> {
+        let form = DataView::build_form_view(data_view_manager, data_view, FormType::Instance)?;
+        let mut lists = json!({});
+
+        for field in form.fields.iter().filter(|field| field.list_items.is_some()) {
+            lists[&field.id] = json!(data_view_manager.renderer.render_list(field));
+        }
+
+        Ok(lists)
+    }
+
+    fn method_from_action(action: &DataViewProcessAction) -> &'static str {
+        match action {
+            DataViewProcessAction::New => "post",
+            DataViewProcessAction::Edit => "put",
+            _ => "get",
+        }
+    }
+
+    // data views without path (ObjectProperty) are saved inside the parent, which is checked by itself
+    fn is_allowed(&self, server_connection: &ServerConnection, method: &str) -> bool {
+        match &self.path {
+            Some(path) => server_connection.is_allowed(path, method),
+            None => true,
+        }
+    }
+
+    fn check_access(&self, server_connection: &ServerConnection, method: &str) -> Result<(), Box<dyn std::error::Error>> {
+        match &self.path {
+            Some(path) => server_connection.check_access(path, method),
+            None => Ok(()),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.original = json!({});
+        self.instance = json!({});
+        self.instance_flags.clear();
+        self.field_external_references_str.clear();
+    }
+
+    // child rows edited inline, in the table of the parent form
+    fn is_grid(&self) -> bool {
+        self.data_view_id.parent_name.is_some() && (self.typ == DataViewType::Dependent || self.typ == DataViewType::ObjectProperty)
+    }
+
+    // x-orderColumn of the schema, or of the array property for ObjectProperty, keeps the position of the rows
+    fn order_column(&self) -> Option<String> {
+        self.schema_data.extensions.get("x-orderColumn").and_then(|column| column.as_str()).map(|column| column.to_string())
+    }
+
+    fn sort_rows(&mut self) {
+        let Some(column) = self.order_column() else {
+            return;
+        };
+
+        self.filter_results.sort_by(|a, b| {
+            let a = a.get(&column).and_then(|value| value.as_f64()).unwrap_or(0.0);
+            let b = b.get(&column).and_then(|value| value.as_f64()).unwrap_or(0.0);
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        });
+    }
+
+    fn renumber_rows(&mut self) {
+        let Some(column) = self.order_column() else {
+            return;
+        };
+
+        for (index, row) in self.filter_results.iter_mut().enumerate() {
+            row[&column] = json!(index + 1);
+        }
+    }
+
+    fn row_default(&self) -> Value {
+        let mut row = json!({});
+
+        for (field_name, field) in &self.properties {
+            if let Some(default) = field.as_item().and_then(|field| field.schema_data.default.clone()) {
+                row[field_name] = default;
+            }
+        }
+
+        row
+    }
+
+    // the edited row becomes the instance of the child, as in table-row-edit
+    fn select_row(&mut self, index: usize) -> Result<(), Box<dyn std::error::Error>> {
+        let row = self.filter_results.get(index).context(format!("Missing row {} in {}", index, self.data_view_id.form_id))?.clone();
+        self.active_index = Some(index);
+        self.instance = row;
+        Ok(())
+    }
+
+    // ObjectProperty rows travel inside the parent instance, that recalculates the expressions over them
+    fn sync_grid(&mut self, server_connection: &ServerConnection, watcher: &dyn DataViewWatch, child_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(data_view) = self.childs.iter().find(|item| item.data_view_id.schema_name == child_name) else {
+            return Ok(());
+        };
+
+        let element_id = &HtmlElementId::new(self.data_view_id.schema_name.clone(), None, FormType::Instance, None, None, None);
+
+        // the rows of dependents aren't a field of the parent, only its expressions follow them
+        if data_view.typ != DataViewType::ObjectProperty {
+            for (field_name, value) in self.evaluate_expressions() {
+                self.set_value(server_connection, watcher, &field_name, &value, element_id)?;
+            }
+
+            return Ok(());
+        }
+
+        let rows = json!(data_view.filter_results);
+        self.set_value(server_connection, watcher, child_name, &rows, element_id)
+    }
+
+    // the cells of the grid and the items of the lists are edited apart from the form, so their type and the constraints of the schema are checked before save
+    fn check_cell(field: &Schema, value: &Value) -> Option<String> {
+        if value.is_null() {
+            return None;
+        }
+
+        match &field.schema_kind {
+            SchemaKind::Type(Type::String(typ)) => {
+                let Some(value) = value.as_str() else {
+                    return Some("isn't a text".to_string());
+                };
+
+                let length = value.chars().count();
+
+                if let Some(min_length) = typ.min_length.filter(|min_length| length < *min_length) {
+                    return Some(format!("is shorter than the minimum length of {}", min_length));
+                }
+
+                if let Some(max_length) = typ.max_length.filter(|max_length| length > *max_length) {
+                    return Some(format!("exceeds the maximum length of {}", max_length));
+                }
+
+                if !typ.enumeration.is_empty() && !typ.enumeration.iter().flatten().any(|option| option == value) {
+                    let options = typ.enumeration.iter().flatten().cloned().collect::<Vec<String>>();
+                    return Some(format!("isn't one of {}", options.join(", ")));
+                }
+
+                match typ.pattern.as_ref().map(|pattern| (pattern, regex::Regex::new(pattern))) {
+                    Some((pattern, Ok(re))) if !re.is_match(value) => Some(format!("don't match the pattern {}", pattern)),
+                    Some((pattern, Err(err))) => Some(format!("can't be checked by the pattern {} : {}", pattern, err)),
+                    _ => None,
+                }
+            }
+            SchemaKind::Type(Type::Integer(_)) if !value.is_i64() && !value.is_u64() => Some("isn't an integer".to_string()),
+            SchemaKind::Type(Type::Integer(typ)) => {
+                if !typ.enumeration.is_empty() && !typ.enumeration.iter().flatten().any(|option| value.as_i64() == Some(*option)) {
+                    let options = typ.enumeration.iter().flatten().map(|option| option.to_string()).collect::<Vec<String>>();
+                    return Some(format!("isn't one of {}", options.join(", ")));
+                }
+
+                let minimum = typ.minimum.map(|minimum| minimum as f64);
+                let maximum = typ.maximum.map(|maximum| maximum as f64);
+                DataView::check_range(value.as_f64().unwrap_or_default(), minimum, typ.exclusive_minimum, maximum, typ.exclusive_maximum)
+            }
+            SchemaKind::Type(Type::Number(typ)) => {
+                let Some(value) = value.as_f64() else {
+                    return Some("isn't a number".to_string());
+                };
+
+                if !typ.enumeration.is_empty() && !typ.enumeration.iter().flatten().any(|option| *option == value) {
+                    let options = typ.enumeration.iter().flatten().map(|option| option.to_string()).collect::<Vec<String>>();
+                    return Some(format!("isn't one of {}", options.join(", ")));
+                }
+
+                DataView::check_range(value, typ.minimum, typ.exclusive_minimum, typ.maximum, typ.exclusive_maximum)
+            }
+            SchemaKind::Type(Type::Boolean {}) if !value.is_boolean() => Some("isn't a boolean".to_string()),
+            _ => None,
+        }
+    }
+
+    fn check_range(value: f64, minimum: Option<f64>, exclusive_minimum: bool, maximum: Option<f64>, exclusive_maximum: bool) -> Option<String> {
+        match (minimum, maximum) {
+            (Some(minimum), _) if value < minimum || (exclusive_minimum && value <= minimum) => Some(format!("is less than the minimum of {}", minimum)),
+            (_, Some(maximum)) if value > maximum || (exclusive_maximum && value >= maximum) => Some(format!("is greater than the maximum of {}", maximum)),
+            _ => None,
+        }
+    }
+
+    // required values of the rows of the inline grids, reported by row as the failures of save
+    fn check_grid_rows(&self) -> Vec<DataViewSaveResult> {
+        let mut save_results = vec![];
+
+        for data_view in self.childs.iter().filter(|item| item.is_grid()) {
+            for (index, row) in data_view.filter_results.iter().enumerate() {
+                for (field_name, field) in &data_view.properties {
+                    let Some(field) = field.as_item() else {
+                        continue;
+                    };
+
+                    let extensions = &field.schema_data.extensions;
+                    let essential = extensions.get("x-essential").and_then(|value| value.as_bool()).unwrap_or(false);
+                    let hidden = extensions.get("x-hidden").and_then(|value| value.as_bool()).unwrap_or(false);
+                    // the key to the parent is filled when the parent is saved
+                    let is_parent_key = extensions
+                        .get("x-$ref")
+                        .and_then(|reference| reference.as_str())
+                        .is_some_and(|reference| OpenAPI::get_schema_name_from_ref(reference) == self.data_view_id.schema_name);
+
+                    let value = row.get(field_name).unwrap_or(&Value::Null);
+
+                    if essential && !hidden && !is_parent_key && !field.schema_data.nullable && value.is_null() {
+                        let error = format!("Missing value of {} in row {}", field_name, index + 1);
+                        save_results.push(DataViewSaveResult::new(&data_view.data_view_id.form_id, Some(index), None, Some(error)));
+                    } else if let Some(reason) = DataView::check_cell(field, value) {
+                        let error = format!("Value of {} in row {} {}", field_name, index + 1, reason);
+                        save_results.push(DataViewSaveResult::new(&data_view.data_view_id.form_id, Some(index), None, Some(error)));
+                    }
+                }
+            }
+        }
+
+        save_results
+    }
+
+    // values of obj that prefill a new record, without the identity columns, the x-noClone fields and optionally the primary key
+    fn clone_values(&self, server_connection: &ServerConnection, obj: &Value, strip_primary_key: bool) -> Value {
+        let primary_keys = match server_connection.service_map.get(&self.data_view_id.schema_name) {
+            Some(service) if strip_primary_key => service.primary_keys.clone(),
+            _ => vec![],
+        };
+
+        let mut values = json!({});
+
+        let Some(obj) = obj.as_object() else {
+            return values;
+        };
+
+        for (field_name, value) in obj {
+            let stripped = self.properties.get(field_name).and_then(|field| field.as_item()).is_some_and(|field| {
+                let extensions = &field.schema_data.extensions;
+                extensions.contains_key("x-identityGeneration") || extensions.get("x-noClone").and_then(|value| value.as_bool()).unwrap_or(false)
+            });
+
+            if stripped || primary_keys.contains(field_name) {
+                continue;
+            }
+
+            // the rows of the ObjectProperty childs are copied with the rules of their schema
+            let child = self.childs.iter().find(|child| child.typ == DataViewType::ObjectProperty && &child.data_view_id.schema_name == field_name);
+
+            values[field_name] = match (child, value.as_array()) {
+                (Some(child), Some(items)) => Value::Array(items.iter().map(|item| child.clone_values(server_connection, item, false)).collect()),
+                _ => value.clone(),
+            };
+        }
+
+        values
+    }
+
+    // the stored row obj with field_name set to value, after the same rules, expressions and checks of the form
+    fn bulk_update_row(&mut self, server_connection: &ServerConnection, watcher: &Box<dyn DataViewWatch>, obj: &Value, field_name: &str, value: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+        let element_id = &HtmlElementId::new(self.data_view_id.schema_name.clone(), None, FormType::Instance, None, None, None);
+        self.clear();
+        self.set_values(server_connection, watcher, obj, element_id)?;
+
+        if self.evaluate_field_states().get(field_name).is_some_and(|(_, disabled, _)| *disabled) {
+            Err(format!("Field {} is read only in this row", field_name))?;
+        }
+
+        self.set_value(server_connection, watcher.as_ref(), field_name, value, element_id)?;
+        self.check_field_states()?;
+
+        if !watcher.check_save(self, None, server_connection, element_id)?.0 {
+            Err(format!("Update of {} refused", field_name))?;
+        }
+
+        Ok(self.instance.clone())
+    }
+
+    // scalar fields that can be set at once in the selected rows
+    fn bulk_fields(&self, server_connection: &ServerConnection, locale: &Locale) -> Vec<(String, String)> {
+        let primary_keys = server_connection.service_map.get(&self.data_view_id.schema_name).map(|service| service.primary_keys.clone()).unwrap_or_default();
+        let mut fields = vec![];
+
+        for (field_name, field) in &self.properties {
+            let Some(field) = field.as_item() else {
+                continue;
+            };
+
+            let extensions = &field.schema_data.extensions;
+            let hidden = extensions.get("x-hidden").and_then(|value| value.as_bool()).unwrap_or(false);
+            let scalar = matches!(&field.schema_kind, SchemaKind::Type(Type::String(_) | Type::Number(_) | Type::Integer(_) | Type::Boolean {}));
+
+            if hidden || !scalar || primary_keys.contains(field_name) || extensions.contains_key("x-expression") || DataView::file_encoding(field).is_some() {
+                continue;
+            }
+
+            fields.push((field_name.clone(), DataView::label_from_schema_data(&field.schema_data, field_name, locale)));
+        }
+
+        fields
+    }
+
+    // csv of the selected rows, with the columns and the texts of the table
+    fn export_selected(&self, server_connection: &ServerConnection, locale: &Locale) -> Result<String, Box<dyn std::error::Error>> {
+        fn escape(str: &str) -> String {
+            if str.contains(&[',', '"', '\n'][..]) {
+                format!("\"{}\"", str.replace('"', "\"\""))
+            } else {
+                str.to_string()
+            }
+        }
+
+        let schema_name = &self.data_view_id.schema_name;
+        let service = server_connection.service_map.get(schema_name).context(format!("Missing service {} in service_map", schema_name))?;
+        let mut lines = vec![];
+
+        let header = self
+            .fields_table
+            .iter()
+            .map(|field_name| match self.properties.get(field_name).and_then(|field| field.as_item()) {
+                Some(field) => escape(&DataView::label_from_schema_data(&field.schema_data, field_name, locale)),
+                None => escape(&field_name.to_case(convert_case::Case::Title)),
+            })
+            .collect::<Vec<String>>();
+
+        lines.push(header.join(","));
+
+        for primary_key in &self.selected_rows {
+            let Some(item) = service.find_one(primary_key) else {
+                continue;
+            };
+
+            let mut cells = vec![];
+
+            for field_name in &self.fields_table {
+                cells.push(escape(&Service::build_field_str(server_connection, &None, schema_name, field_name, item)?));
+            }
+
+            lines.push(cells.join(","));
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    fn build_changes(&mut self, element_id: &HtmlElementId, data_out: &mut Value) -> Result<(), Box<dyn std::error::Error>> {
+        let mut form = json!({});
+
+        for (field_name, value) in &self.properties_modified {
+            form[field_name] = json!(value);
+        }
+
+        let form_id = format!("{}-{}", element_id.form_type, self.data_view_id.form_id);
+        data_out[form_id] = form;
+        self.properties_modified.clear();
+
+        for data_view in &mut self.childs {
+            data_view.build_changes(element_id, data_out)?;
+        }
+
+        Ok(())
+    }
+
+    fn build_form(data_view_manager: &DataViewManager, data_view: &DataView, form_type: FormType) -> Result<String, Box<dyn std::error::Error>> {
+        let form = DataView::build_form_view(data_view_manager, data_view, form_type)?;
+        Ok(data_view_manager.renderer.render_form(&form))
+    }
+
+    fn build_form_view(data_view_manager: &DataViewManager, data_view: &DataView, form_type: FormType) -> Result<FormView, Box<dyn std::error::Error>> {
+        let server_connection = &data_view_manager.server_connection;
+        let locale = &data_view_manager.locale;
+        let form_id = &data_view.data_view_id.form_id;
+        let form_type_str = form_type.to_string();
+        let mut fields = vec![];
+
+        let field_states = if form_type == FormType::Instance {
+            data_view.evaluate_field_states()
+        } else {
+            IndexMap::default()
+        };
+
+        for (field_name, field) in &data_view.properties {
+            let field = field.as_item().context("field is reference")?;
+            let extension = &field.schema_data.extensions;
+            let hidden = extension.get("x-hidden").unwrap_or(&Value::Bool(false)).as_bool().unwrap_or(false);
+
+            if hidden {
+                continue;
+            }
+
+            let list_item = DataView::list_item_schema(field).map(|(_, item)| item);
+
+            // scalar arrays use the input of the item, and don't take part in the search forms
+            let schema_kind = match list_item {
+                Some(_) if form_type != FormType::Instance => continue,
+                Some(item) => &item.schema_kind,
+                None => &field.schema_kind,
+            };
+
+            let typ = match schema_kind {
+                SchemaKind::Type(typ) => typ,
+                SchemaKind::Any(_) => todo!(),
+                _ => continue,
+            };
+
+            let (input_type, step, pattern, max_length, col_size, rangeable) = match typ {
+                Type::String(typ) => {
+                    let max_length = typ.max_length.unwrap_or(1024);
+
+                    let col_size = if max_length > 110 { 11 } else { (max_length / 10) + 1 };
+
+                    let (input_type, is_rangeable) = match &typ.format {
+                        VariantOrUnknownOrEmpty::Item(format) => match format {
+                            StringFormat::Date => ("date", true),
+                            StringFormat::DateTime => ("datetime-local", true),
+                            StringFormat::Password => ("text", false),
+                            StringFormat::Byte => ("file", false),
+                            StringFormat::Binary => ("file", false),
+                        },
+                        _ => ("text", false),
+                    };
+
+                    (input_type, None, None, max_length, col_size, is_rangeable)
+                }
+                Type::Number(_typ) => {
+                    let precision: usize = extension.get("x-precision").unwrap_or(&json!(12)).as_u64().unwrap_or(12).try_into().unwrap_or(12);
+                    let scale = if let Some(scale) = extension.get("x-scale") {
+                        match scale.as_u64().unwrap_or(3) {
+                            1 => "0.1",
+                            3 => "0.001",
+                            4 => "0.0001",
+                            5 => "0.00001",
+                            _ => "0.01",
+                        }
+                    } else {
+                        "0.01"
+                    };
+
+                    ("number", Some(scale.to_string()), None, precision, 2, true)
+                }
+                Type::Integer(_typ) => {
+                    if let Some(_reference) = extension.get("x-$ref") {
+                        ("text", None, None, 1024, 8, false)
+                    } else {
+                        ("number", Some("1".to_string()), Some(r"\d+".to_string()), 15, 2, true)
+                    }
+                }
+                Type::Boolean {} => ("checkbox", None, None, 0, 1, false),
+                Type::Object(_) => continue,
+                Type::Array(_) => continue,
+            };
+
+            let options = if data_view.action != DataViewProcessAction::View {
+                data_view.field_results_str.get(field_name).cloned().unwrap_or_default()
+            } else {
+                vec![]
+            };
+
+            // long reference lists are not embedded, the input asks for suggestions while typing,
+            // as also the references to services that aren't kept in cache, searched in the server
+            let remote = match extension.get("x-$ref").and_then(|reference| reference.as_str()) {
+                Some(reference) => server_connection.service_map.get(&OpenAPI::get_schema_name_from_ref(reference)).is_some_and(|service| !service.loaded),
+                None => false,
+            };
+
+            let typeahead = data_view.action != DataViewProcessAction::View && extension.contains_key("x-$ref") && (options.len() > TYPEAHEAD_MIN_OPTIONS || remote);
+            let select = data_view.action != DataViewProcessAction::View && !typeahead && options.len() > 0 && options.len() <= TYPEAHEAD_MIN_OPTIONS;
+            let options = if typeahead { vec![] } else { options };
+
+            let reference_path = if let Some(reference) = extension.get("x-$ref") {
+                let reference = reference.as_str().context("not string content")?;
+                server_connection.service_map.get(&OpenAPI::get_schema_name_from_ref(reference)).map(|service| service.path.clone())
+            } else {
+                None
+            };
+
+            let reference = if let Some(reference_path) = reference_path.filter(|path| server_connection.is_allowed(path, "get")) {
+                let (create_id, search_id) = if data_view.action != DataViewProcessAction::View {
+                    let create_id = if server_connection.is_allowed(&reference_path, "post") {
+                        Some(format!("reference-create-{form_id}-{field_name}"))
+                    } else {
+                        None
+                    };
+
+                    (create_id, Some(format!("reference-search-{form_id}-{field_name}")))
+                } else {
+                    (None, None)
+                };
+
+                Some(ReferenceView {
+                    view_id: format!("reference-view-{form_id}-{field_name}"),
+                    create_id,
+                    search_id,
+                })
+            } else {
+                None
+            };
+
+            let flags = if let Some(flags) = extension.get("x-flags") {
+                let flags = flags.as_array().context(format!("Not array content in extension 'x-flags' of field {}, content : {}", field_name, flags))?;
+                let mut list = vec![];
+
+                for label in flags {
+                    list.push(label.as_str().context("not string content")?.to_string());
+                }
+
+                list
+            } else {
+                vec![]
+            };
+
+            let range_options = if ["date", "datetime-local"].contains(&input_type) {
+                let options: &[&str] = match form_type {
+                    FormType::Filter => &i18n::FILTER_RANGES,
+                    FormType::Aggregate => &i18n::AGGREGATE_RANGES,
+                    _ => &[],
+                };
+
+                options.iter().map(|option| (option.to_string(), locale.translate(option))).collect()
+            } else {
+                vec![]
+            };
+
+            let list_items = list_item.map(|_| {
+                let list = data_view.instance.get(field_name).and_then(|value| value.as_array()).cloned().unwrap_or_default();
+
+                list.iter()
+                    .map(|value| match value {
+                        Value::String(str) => str.clone(),
+                        Value::Null => "".to_string(),
+                        _ => value.to_string(),
+                    })
+                    .collect()
+            });
+
+            let file = match DataView::file_encoding(field) {
+                Some(encoding) if form_type == FormType::Instance => {
+                    let max_size = match &field.schema_kind {
+                        SchemaKind::Type(Type::String(typ)) => typ.max_length,
+                        _ => None,
+                    };
+
+                    Some(FileView {
+                        encoding: encoding.to_string(),
+                        media_type: DataView::file_media_type(field),
+                        max_size,
+                        href: DataView::file_href(field, data_view.instance.get(field_name).unwrap_or(&Value::Null)),
+                        download_label: locale.translate("download"),
+                    })
+                }
+                // the content of files can't be searched
+                Some(_) => continue,
+                None => None,
+            };
+
+            // masked values are shown formatted, so they need a text input, even for numbers, which stay rangeable
+            let (input_type, step, pattern, max_length, rangeable) = if mask::has_mask(extension) {
+                ("text", None, None, mask::max_length(extension, max_length), rangeable && mask::is_number_format(extension))
+            } else {
+                (input_type, step, pattern, max_length, rangeable)
+            };
+
+            let essential = extension.get("x-essential").unwrap_or(&Value::Bool(false)).as_bool().unwrap_or(false);
+            let (hidden, read_only, required) = field_states.get(field_name).cloned().unwrap_or((false, false, essential && !field.schema_data.nullable));
+            let col_size = extension.get("x-colSize").and_then(|value| value.as_u64()).map(|value| value.clamp(1, 12) as usize).unwrap_or(col_size);
+
+            // the search forms are always a plain list
+            let (tab, group, row) = if form_type == FormType::Instance {
+                (
+                    extension.get("x-tab").and_then(|value| locale.localize(value)),
+                    extension.get("x-group").and_then(|value| locale.localize(value)),
+                    extension.get("x-row").and_then(|value| value.as_u64()).map(|value| value as usize),
+                )
+            } else {
+                (None, None, None)
+            };
+
+            fields.push(FieldView {
+                id: format!("{form_type_str}-{form_id}-{field_name}"),
+                name: field_name.clone(),
+                label: DataView::label_from_schema_data(&field.schema_data, field_name, locale),
+                help: DataView::help_from_schema_data(&field.schema_data, locale),
+                input_type: input_type.to_string(),
+                step,
+                pattern,
+                max_length,
+                col_size,
+                rangeable,
+                required: form_type == FormType::Instance && required && !hidden,
+                disabled: data_view.action == DataViewProcessAction::View || extension.contains_key("x-expression") || read_only,
+                hidden,
+                select,
+                typeahead,
+                options,
+                reference,
+                flags,
+                range_options,
+                range_to: locale.translate("range_to"),
+                tab,
+                group,
+                row,
+                list_items,
+                file,
+                placeholder: mask::placeholder(extension, locale),
+                value: None,
+            });
+        }
+
+        let embedded = data_view.typ == DataViewType::ObjectNested;
+
+        let (label, href_new, childs, filter, aggregate, sort) = match form_type {
+            FormType::Instance if embedded => {
+                let label = DataView::label_from_schema_data(&data_view.schema_data, &data_view.data_view_id.schema_name, locale);
+                (label, None, vec![], None, None, None)
+            }
+            FormType::Instance => {
+                let mut childs = vec![];
+
+                for data_view in &data_view.childs {
+                    childs.push(DataView::build_form_view(data_view_manager, data_view, FormType::Instance)?);
+                }
+
+                let label = DataView::label_from_schema_data(&data_view.schema_data, &data_view.data_view_id.schema_name, locale);
+
+                let href_new = if data_view.is_allowed(server_connection, "post") {
+                    Some(DataView::build_location_hash(&data_view.data_view_id.form_id, "new", &json!({}))?)
+                } else {
+                    None
+                };
+
+                let filter = DataView::build_form_view(data_view_manager, data_view, FormType::Filter)?;
+                let aggregate = DataView::build_form_view(data_view_manager, data_view, FormType::Aggregate)?;
+                let sort = DataView::build_form_view(data_view_manager, data_view, FormType::Sort)?;
+                (label, href_new, childs, Some(Box::new(filter)), Some(Box::new(aggregate)), Some(Box::new(sort)))
+            }
+            FormType::Filter => (locale.translate("filter"), None, vec![], None, None, None),
+            FormType::Aggregate => (locale.translate("aggregate"), None, vec![], None, None, None),
+            FormType::Sort => (locale.translate("sort"), None, vec![], None, None, None),
+        };
+
+        let mut actions = vec![];
+
+        // the fields of a nested object are applied with the parent form
+        if !embedded {
+            if form_type != FormType::Instance || data_view.is_allowed(server_connection, "post") || data_view.is_allowed(server_connection, "put") {
+                actions.push(ActionView {
+                    id: format!("apply-{form_type_str}-{form_id}"),
+                    name: "apply".to_string(),
+                    label: locale.translate("apply"),
+                    icon: "apply".to_string(),
+                    primary: true,
+                });
+            }
+
+            actions.push(ActionView {
+                id: format!("clear-{form_type_str}-{form_id}"),
+                name: "clear".to_string(),
+                label: locale.translate("clear"),
+                icon: "erase".to_string(),
+                primary: false,
+            });
+
+            actions.push(ActionView {
+                id: format!("cancel-{form_type_str}-{form_id}"),
+                name: "cancel".to_string(),
+                label: locale.translate("cancel"),
+                icon: "exit".to_string(),
+                primary: false,
+            });
+
+            // an opened record can be the start of a new one
+            if form_type == FormType::Instance && data_view.path.is_some() && data_view.is_allowed(server_connection, "post") {
+                actions.push(ActionView {
+                    id: format!("clone-{form_type_str}-{form_id}"),
+                    name: "clone".to_string(),
+                    label: locale.translate("clone"),
+                    icon: "copy".to_string(),
+                    primary: false,
+                });
+            }
+
+            if form_type == FormType::Instance && data_view.is_allowed(server_connection, "delete") {
+                actions.push(ActionView {
+                    id: format!("delete-{form_type_str}-{form_id}"),
+                    name: "delete".to_string(),
+                    label: locale.translate("delete"),
+                    icon: "remove".to_string(),
+                    primary: false,
+                });
+            }
+        }
+
+        Ok(FormView {
+            form_type,
+            form_id: form_id.clone(),
+            id: format!("{form_type_str}-{form_id}"),
+            label,
+            hidden: !embedded && !data_view_manager.data_view_map.contains_key(&data_view.data_view_id.form_id),
+            embedded,
+            href_new,
+            fields,
+            actions,
+            childs,
+            filter,
+            aggregate,
+            sort,
+        })
     }
 
     fn build_table(data_view_manager: &DataViewManager, data_view: &DataView, params_search: &DataViewProcessParams) -> Result<String, Box<dyn std::error::Error>> {
+        let Some(table) = DataView::build_table_view(data_view_manager, data_view, params_search)? else {
+            return Ok("".to_string());
+        };
+
+        Ok(data_view_manager.renderer.render_table(&table))
+    }
+
+    fn build_table_view(data_view_manager: &DataViewManager, data_view: &DataView, params_search: &DataViewProcessParams) -> Result<Option<TableView>, Box<dyn std::error::Error>> {
         fn build_href(data_view_manager: &DataViewManager, data_view: &DataView, item: &Value, action: &str) -> Result<String, Box<dyn std::error::Error>> {
             let str = if data_view.path.is_some() {
                 let service = data_view_manager.server_connection.service_map.get(&data_view.data_view_id.schema_name).context("Missing service")?;
@@ -1299,9 +2189,18 @@ impl DataView {
             Ok(str)
         }
 
-        let form_id = &data_view.data_view_id.form_id;
+        let locale = &data_view_manager.locale;
+
+        let trash = match data_view_manager.server_connection.service_map.get(&data_view.data_view_id.schema_name) {
+            Some(service) if data_view.typ == DataViewType::Primary && data_view.path.is_some() && service.soft_delete.is_some() => Some(data_view.trash),
+            _ => None,
+        };
 
-        let list = if data_view.path.is_none() || data_view.filter_results.len() > 0 {
+        let list = if trash == Some(true) {
+            let schema_name = &data_view.data_view_id.schema_name;
+            let service = data_view_manager.server_connection.service_map.get(schema_name).context("broken service")?;
+            &service.list_deleted
+        } else if data_view.uses_filter_results() {
             &data_view.filter_results
         } else {
             let schema_name = &data_view.data_view_id.schema_name;
@@ -1309,24 +2208,38 @@ impl DataView {
             &service.list
         };
 
-        if list.len() == 0 {
-            return Ok("".to_string());
+        let editable = data_view.is_grid()
+            && data_view.is_allowed(&data_view_manager.server_connection, "post")
+            && data_view_manager
+                .data_view_map
+                .get(&data_view.data_view_id.form_id_parent)
+                .is_some_and(|parent| parent.action == DataViewProcessAction::New || parent.action == DataViewProcessAction::Edit);
+
+        // the table keeps the link of the trash while empty
+        if list.len() == 0 && !editable && trash.is_none() {
+            return Ok(None);
         }
 
-        let mut hmtl_header = vec![];
+        // the cells take the controls of the instance form of the child
+        let controls = if editable {
+            DataView::build_form_view(data_view_manager, data_view, FormType::Instance)?.fields
+        } else {
+            vec![]
+        };
+
+        let mut columns = vec![];
 
         for field_name in &data_view.fields_table {
-            let label = field_name.to_case(convert_case::Case::Title);
-            let col = format!(
-                r##"
-            <th>
-                <a href id="sort_left-{form_id}-{field_name}"><i class="bi bi-arrow-left"></i> </a>
-                <a href id="sort_toggle-{form_id}-{field_name}"> {label}</a>
-                <a href id="sort_rigth-{form_id}-{field_name}"><i class="bi bi-arrow-right"></i> </a>
-            </th>
-            "##
-            );
-            hmtl_header.push(col);
+            let (label, help) = match data_view.properties.get(field_name).and_then(|field| field.as_item()) {
+                Some(field) => (DataView::label_from_schema_data(&field.schema_data, field_name, locale), DataView::help_from_schema_data(&field.schema_data, locale)),
+                None => (field_name.to_case(convert_case::Case::Title), None),
+            };
+
+            columns.push(ColumnView {
+                field_name: field_name.clone(),
+                label,
+                help,
+            });
         }
 
         let mut offset_ini = (data_view.current_page - 1) * data_view.page_size;
@@ -1341,121 +2254,172 @@ impl DataView {
             offset_end = list.len();
         }
 
-        let mut hmtl_rows = vec![];
+        // the list opened by reference-search keeps the selection links while it is paginated, sorted or filtered
+        let select_out = params_search.select_out.clone().or_else(|| {
+            data_view_manager
+                .reference_origins
+                .get(&data_view.data_view_id.form_id)
+                .filter(|origin| origin.select)
+                .and_then(|origin| origin.element_id.field_name.clone())
+        });
+
+        // the rows of the lists are selected by primary key, that is kept while sorting, filtering and paginating
+        let selectable = data_view.typ == DataViewType::Primary && data_view.path.is_some() && select_out.is_none() && trash != Some(true);
+        let service = data_view_manager.server_connection.service_map.get(&data_view.data_view_id.schema_name);
+        let clonable = selectable && data_view.is_allowed(&data_view_manager.server_connection, "post");
+        let restorable = trash == Some(true) && data_view.is_allowed(&data_view_manager.server_connection, "delete");
+
+        let mut rows = vec![];
         let mut item_index = 0;
 
         for index in offset_ini..offset_end {
             let item = list.get(index).context(format!("Broken: missing item at index"))?;
-            let mut html_cols = vec![];
+            let mut cells = vec![];
 
             for field_name in &data_view.fields_table {
                 let href_go_to_field = data_view.build_go_to_field(&data_view_manager.server_connection, field_name, "view", item, false)?;
-                let href_go_to_field = href_go_to_field.unwrap_or("".to_string());
-
                 let parent_name = if data_view.path.is_none() { &data_view.data_view_id.parent_name } else { &None };
-                let field_str = Service::build_field_str(&data_view_manager.server_connection, parent_name, &data_view.data_view_id.schema_name, field_name, item)?;
-                html_cols.push(format!(r#"<td><a id="table-row-col-{form_id}-{field_name}-{index}" href="{href_go_to_field}">{field_str}</a></td>"#));
+                let field = data_view.properties.get(field_name).and_then(|field| field.as_item());
+
+                // files are linked for download instead of printing the content
+                let cell = match field.and_then(|field| DataView::file_href(field, item.get(field_name).unwrap_or(&Value::Null))) {
+                    Some(href) => CellView {
+                        field_name: field_name.clone(),
+                        href,
+                        text: locale.translate("download"),
+                        download: true,
+                        control: None,
+                    },
+                    None => {
+                        let field_str = Service::build_field_str(&data_view_manager.server_connection, parent_name, &data_view.data_view_id.schema_name, field_name, item)?;
+                        let is_option = field.is_some_and(|field| field.schema_data.extensions.contains_key("x-$ref") || field.schema_data.extensions.contains_key("x-enum"));
+
+                        let control = controls.iter().find(|control| &control.name == field_name && control.file.is_none() && control.list_items.is_none()).map(|control| {
+                            // references and enums are edited by their description, like in the form
+                            let value = match item.get(field_name).unwrap_or(&Value::Null) {
+                                _ if is_option => field_str.clone(),
+                                Value::String(str) => str.clone(),
+                                Value::Null => "".to_string(),
+                                value => value.to_string(),
+                            };
+
+                            FieldView {
+                                id: format!("table-row-col-{}-{}-{}", data_view.data_view_id.form_id, field_name, index),
+                                value: Some(value),
+                                reference: None,
+                                ..control.clone()
+                            }
+                        });
+
+                        CellView {
+                            field_name: field_name.clone(),
+                            href: href_go_to_field.unwrap_or("".to_string()),
+                            text: field_str,
+                            download: false,
+                            control,
+                        }
+                    }
+                };
+
+                cells.push(cell);
             }
 
-            let html_cols = html_cols.join("\n");
+            let href_edit = if data_view.is_allowed(&data_view_manager.server_connection, "put") {
+                Some(build_href(data_view_manager, data_view, item, "edit")?)
+            } else {
+                None
+            };
 
-            let html_a_search_select = if let Some(select_out) = &params_search.select_out {
-                format!(r#"<a href id="search_select-{form_id}-{select_out}-{item_index}"><i class="bi bi-ok"></i> Select</a>"#)
+            let selected = if selectable {
+                let primary_key = service.and_then(|service| service.get_primary_key(item));
+                Some(primary_key.is_some_and(|primary_key| data_view.selected_rows.contains(&primary_key)))
             } else {
-                "".to_string()
+                None
             };
 
-            let href_view = build_href(data_view_manager, data_view, item, "view")?;
-            let href_edit = build_href(data_view_manager, data_view, item, "edit")?;
-            let href_item_move = format!(
-                r##"
-            <a id="table-row-remove-{form_id}-{index}" ng-if="edit == true" href><i class="bi bi-trash"></i> Delete</a>
-            <a id="table-row-up-{form_id}-{index}"     ng-if="edit == true" href><i class="bi bi-arrow-up"></i> Up</a>
-            <a id="table-row-down-{form_id}-{index}"   ng-if="edit == true" href><i class="bi bi-arrow-down"></i> Down</a>
-            "##
-            );
-            let row = format!(
-                r##"
-            <tr>
-                <td>
-                    <a id="table-row-view-{form_id}-{index}" href="{href_view}"><i class="bi bi-eye-open"></i> View</a>
-                    <a id="table-row-edit-{form_id}-{index}" href="{href_edit}"><i class="bi bi-eye-open"></i> Edit</a>
-                    {html_a_search_select}
-                    {href_item_move}
-                </td>
-                {html_cols}
-            </tr>
-            "##
-            );
-            hmtl_rows.push(row);
+            rows.push(RowView {
+                index,
+                href_view: build_href(data_view_manager, data_view, item, "view")?,
+                href_edit,
+                select_out: select_out.clone(),
+                item_index,
+                movable: editable,
+                selected,
+                clonable,
+                restorable,
+                cells,
+            });
             item_index += 1;
         }
 
-        let html_page_control = if list.len() > data_view.page_size {
+        let pagination = if list.len() > data_view.page_size {
             let max_page = if list.len() % data_view.page_size == 0 {
                 list.len() / data_view.page_size
             } else {
                 (list.len() / data_view.page_size) + 1
             };
 
-            let mut html_pages = vec![];
-
-            for page in 1..max_page {
-                html_pages.push(format!(r##"<li class="page-item"><a class="page-link" id="selected_page-{form_id}-{page}" href="#">{page}</a></li>"##));
-            }
-
-            let html_pages = html_pages.join("\n");
-            let page_size = data_view.page_size;
-            format!(
-                r##"
-            <nav aria-label="Page navigation">
-                <ul class="pagination">
-                    <li class="page-item">
-                        <a class="page-link" href="#" aria-label="Previous">
-                            <span aria-hidden="true">&laquo;</span>
-                        </a>
-                    </li>
-                    {html_pages}
-                    <li class="page-item">
-                        <a class="page-link" href="#" aria-label="Next">
-                            <span aria-hidden="true">&raquo;</span>
-                        </a>
-                    </li>
-                </ul>
-            </nav>
-
-            <div class="form-group row" ng-if="vm.filterResults.length > vm.pageSize">
-                <label for="page-size" class="col-2 col-form-label">Page size</label>
-
-                <div class="col-2">
-                    <input class="form-control" id="page_size-{form_id}" name="page_size" type="number" step="1" value="{page_size}">
-                </div>
-            </div>
-            "##
-            )
+            Some(PaginationView {
+                pages: (1..max_page).collect(),
+                page_size: data_view.page_size,
+            })
         } else {
-            "".to_string()
+            None
         };
 
-        let html_header = hmtl_header.join("\n");
-        let html_rows = hmtl_rows.join("\n");
-        let ret = format!(
-            r##"
-            <table id="table-{form_id}" class="table table-responsive table-bordered table-striped clearfix">
-                <thead>
-                    <tr>
-                        <th></th>
-                        {html_header}
-                    </tr>
-                </thead>
-                <tbody>
-                {html_rows}
-                </tbody>
-            </table>
-            {html_page_control}
-        "##
-        );
-        Ok(ret)
+        let bulk = if selectable {
+            let server_connection = &data_view_manager.server_connection;
+
+            let options = match &data_view.bulk_field {
+                Some(field_name) => data_view.field_results_str.get(field_name).cloned().unwrap_or_default(),
+                None => vec![],
+            };
+
+            Some(BulkView {
+                selected: data_view.selected_rows.len(),
+                matching: list.len(),
+                removable: data_view.is_allowed(server_connection, "delete"),
+                updatable: data_view.is_allowed(server_connection, "put"),
+                fields: data_view.bulk_fields(server_connection, locale),
+                field: data_view.bulk_field.clone(),
+                // long reference lists aren't embedded
+                options: if options.len() > TYPEAHEAD_MIN_OPTIONS { vec![] } else { options },
+            })
+        } else {
+            None
+        };
+
+        Ok(Some(TableView {
+            form_id: data_view.data_view_id.form_id.clone(),
+            columns,
+            rows,
+            pagination,
+            labels: TableLabels {
+                view: locale.translate("view"),
+                edit: locale.translate("edit"),
+                clone: locale.translate("clone"),
+                select: locale.translate("select"),
+                remove: locale.translate("row_remove"),
+                up: locale.translate("row_up"),
+                down: locale.translate("row_down"),
+                add: locale.translate("row_add"),
+                page_size: locale.translate("page_size"),
+                selected: locale.translate("selected"),
+                select_all: locale.translate("select_all"),
+                select_none: locale.translate("select_none"),
+                bulk_remove: locale.translate("bulk_remove"),
+                bulk_update: locale.translate("bulk_update"),
+                bulk_export: locale.translate("bulk_export"),
+                bulk_field: locale.translate("bulk_field"),
+                bulk_value: locale.translate("bulk_value"),
+                trash: locale.translate("trash"),
+                trash_exit: locale.translate("trash_exit"),
+                restore: locale.translate("restore"),
+            },
+            editable,
+            bulk,
+            trash,
+        }))
     }
 
     fn paginate(&mut self, page_size: Option<usize>, page: Option<usize>) -> Result<(), Box<dyn std::error::Error>> {
@@ -1544,7 +2508,7 @@ impl DataView {
 
     fn apply_aggregate(&mut self, server_connection: &ServerConnection, aggregate: &Value) -> Result<(), Box<dyn std::error::Error>> {
         fn label_from_date(date: DateTime<Local>, range: &str) -> String {
-            let date_ranges = ["secound", "minute", "hora", "dia", "mês", "ano"];
+            let date_ranges = ["second", "minute", "hour", "day", "month", "year"];
             let typ = date_ranges.into_iter().position(|item| item == range).unwrap_or(0);
             let mut list = vec![];
 
@@ -1573,7 +2537,7 @@ impl DataView {
 
         self.aggregate_results = HashMap::default();
 
-        let list = if self.path.is_none() || self.filter_results.len() > 0 {
+        let list = if self.uses_filter_results() {
             &self.filter_results
         } else {
             let service = server_connection.service_map.get(&self.data_view_id.schema_name).context("Missing service in service_map")?;
@@ -1609,7 +2573,7 @@ impl DataView {
                                     StringFormat::Date => {
                                         let from: NaiveDateTime = value.as_str().unwrap_or("2023-01-01").parse()?;
                                         let date = Local.from_local_datetime(&from).unwrap();
-                                        label_from_date(date, range.as_str().unwrap_or_default())
+                                        label_from_date(date, i18n::range_key(range.as_str().unwrap_or_default()).unwrap_or_default())
                                     }
                                     StringFormat::DateTime => todo!(),
                                     StringFormat::Password => todo!(),
@@ -1672,17 +2636,22 @@ impl DataView {
     }
     // Filter section
     fn clear_filter(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // hora corrente, hora anterior, uma hora, hoje, ontem, um dia, semana corrente, semana anterior, uma semana, quinzena corrente, quinzena anterior, 15 dias, mês corrente, mês anterior, 30 dias, ano corrente, ano anterior, 365 dias
         self.instance_filter = json!({});
         self.instance_filter_range = json!({});
         self.instance_filter_range_min = json!({});
         self.instance_filter_range_max = json!({});
         //self.filter_results = self.list.clone();
         //self.filter_results.clear();
+        self.filter_applied = false;
         self.clear();
         Ok(())
     }
 
+    // the rows of the form are in filter_results, otherwise they are the whole list of the service
+    fn uses_filter_results(&self) -> bool {
+        self.path.is_none() || self.typ == DataViewType::Dependent || self.filter_applied || self.filter_results.len() > 0
+    }
+
     fn apply_filter(&mut self, list: &Vec<Value>) {
         fn match_object(expected_fields: &Value, actual_object: &Value, match_string_partial: bool, recursive: bool, compare_type: i8) -> Result<bool, Box<dyn std::error::Error>> {
             for (key, expected_property) in expected_fields.as_object().context("broken")? {
@@ -1812,6 +2781,7 @@ impl DataView {
             }
         }
 
+        self.filter_applied = true;
         self.filter_results = list
             .into_iter()
             .filter(|candidate| {
@@ -2000,9 +2970,11 @@ impl DataView {
                 FormType::Instance => {
                     data_view.instance[field_name] = value;
 
-                    if data_view.typ == DataViewType::ObjectProperty {
-                        if let Some(index) = data_view.active_index {
-                            data_view.filter_results[index] = data_view.instance.clone();
+                    if data_view.is_grid() {
+                        let instance = data_view.instance.clone();
+
+                        if let Some(row) = data_view.active_index.and_then(|index| data_view.filter_results.get_mut(index)) {
+                            *row = instance;
                         }
                     }
                 },
@@ -2099,6 +3071,8 @@ impl DataView {
                 Value::Null
             } else if let Some(value) = data_view.field_external_references_str.get(field_name) {
                 json!(value)
+            } else if let Some(str) = mask::format_value(extensions, &value, &server_connection.locale) {
+                json!(str)
             } else {
                 value.clone()
             };
@@ -2166,22 +3140,80 @@ impl DataView {
                     Value::Object(_obj) => {}
                     _ => set_value_show(data_view, field_name, field_value_str, element_id)?,
                 }
+
+                if data_view.typ == DataViewType::ObjectNested && element_id.form_type == FormType::Instance {
+                    let instance = data_view.instance.clone();
+                    self.instance[child_name] = instance;
+                }
             } else {
                 set_form_type_value(self, &element_id.form_type.clone(), &element_id.form_type_ext.clone(), field_name, field_value.clone())?;
 
                 match &field_value {
                     Value::Array(array) => {
-                        let data_view = self
-                            .childs
-                            .iter_mut()
-                            .find(|item| item.data_view_id.schema_name == field_name)
-                            .context(format!("set_value 3 : Missing item {} in data_view {}", field_name, self.data_view_id.schema_name))?;
-                        data_view.filter_results = array.clone();
+                        if let Some(data_view) = self.childs.iter_mut().find(|item| item.data_view_id.schema_name == field_name) {
+                            data_view.filter_results = array.clone();
+                        } else {
+                            // scalar arrays are edited in the list of the parent form
+                            set_value_show(self, field_name, field_value_str, element_id)?;
+                        }
                     }
                     Value::Object(_obj) => {}
                     _ => set_value_show(self, field_name, field_value_str, element_id)?,
                 }
             }
+
+            // the computed fields are set by the recalculation below, that already reaches the ones depending on them
+            let is_computed = match child_name {
+                Some(child_name) => self.childs.iter().find(|item| item.data_view_id.schema_name == child_name).map(|item| item.is_computed(field_name)).unwrap_or(false),
+                None => self.is_computed(field_name),
+            };
+
+            if element_id.form_type == FormType::Instance && !is_computed {
+                if let Some(child_name) = child_name {
+                    let changes = self.childs.iter().find(|item| item.data_view_id.schema_name == child_name).map(|item| item.evaluate_expressions()).unwrap_or_default();
+
+                    for (field_name, value) in changes {
+                        self.set_value(server_connection, watcher, &field_name, &value, element_id)?;
+                    }
+                }
+
+                let element_id = &HtmlElementId::new(self.data_view_id.schema_name.clone(), None, FormType::Instance, None, None, None);
+
+                for (field_name, value) in self.evaluate_expressions() {
+                    self.set_value(server_connection, watcher, &field_name, &value, element_id)?;
+                }
+            }
+
+            if element_id.form_type == FormType::Instance {
+                let data_view = match child_name {
+                    Some(child_name) => self
+                        .childs
+                        .iter_mut()
+                        .find(|item| item.data_view_id.schema_name == child_name)
+                        .context(format!("set_value 3 : Missing item {} in data_view {}", child_name, self.data_view_id.schema_name))?,
+                    None => &mut *self,
+                };
+
+                let mut cleared = vec![];
+
+                for dependent in data_view.field_filter_dependents(field_name) {
+                    let (list, list_str) = data_view.build_field_filter_result(server_connection, &dependent)?;
+                    let selected = data_view.field_external_references_str.get(&dependent).filter(|str| !str.is_empty());
+                    let nullable = data_view.properties.get(&dependent).and_then(|field| field.as_item()).map(|field| field.schema_data.nullable).unwrap_or(false);
+
+                    // the selection that left the narrowed options is cleared, when the field accepts it
+                    if selected.is_some_and(|str| !list_str.contains(str)) && (nullable || data_view.action == DataViewProcessAction::New) {
+                        cleared.push(dependent.clone());
+                    }
+
+                    data_view.field_results.insert(dependent.clone(), list);
+                    data_view.field_results_str.insert(dependent, list_str);
+                }
+
+                for dependent in cleared {
+                    self.set_value(server_connection, watcher, &dependent, &Value::Null, element_id)?;
+                }
+            }
         }
 
         Ok(())
@@ -2230,7 +3262,7 @@ impl DataView {
         set_values_process(self, None, server_connection, watcher, obj, element_id)?;
 
         for data_view in &mut self.childs {
-            if data_view.typ == DataViewType::ObjectProperty {
+            if data_view.typ == DataViewType::ObjectProperty || data_view.typ == DataViewType::ObjectNested {
                 if let Some(obj) = obj.get(&data_view.data_view_id.schema_name) {
                     //println!("[DEBUG - set_values - 2] {}.instance = {}", data_view.data_view_id.form_id, obj);
                     data_view.set_values(server_connection, watcher, obj, element_id)?;
@@ -2247,10 +3279,241 @@ impl DataView {
             None => None.context("Missing path information")?,
         };
 
+        let instance = self.upload_files(server_connection).await?;
+
         if self.action == DataViewProcessAction::New {
-            server_connection.save(path, &self.instance).await
+            server_connection.save(path, &instance).await
+        } else {
+            server_connection.update(path, &instance).await
+        }
+    }
+
+    // the binary files chosen in the form are uploaded only when the record is saved, so a cancelled form leaves nothing on the server
+    async fn upload_files(&self, server_connection: &ServerConnection) -> Result<Value, Box<dyn std::error::Error>> {
+        let mut instance = self.instance.clone();
+
+        for (field_name, field) in &self.properties {
+            let Some(field) = field.as_item().filter(|field| DataView::file_encoding(field) == Some("binary")) else {
+                continue;
+            };
+
+            let Some((media_type, data)) = instance.get(field_name).and_then(|value| value.as_str()).and_then(|value| value.strip_prefix("data:")).and_then(|value| value.split_once(";base64,")) else {
+                continue;
+            };
+
+            let upload_path = field.schema_data.extensions.get("x-uploadPath").and_then(|path| path.as_str()).context(format!("Missing x-uploadPath in field {}", field_name))?;
+            let data = base64::engine::general_purpose::STANDARD.decode(data)?;
+            let reference = server_connection.upload(upload_path, data, media_type).await?;
+            instance[field_name] = json!(reference);
+        }
+
+        Ok(instance)
+    }
+
+    // undoes the writes of save_with_dependents in the reverse order, returning the failures
+    async fn undo_saves(server_connection: &mut ServerConnection, undo: Vec<SaveUndo>) -> Vec<String> {
+        let mut errors = vec![];
+
+        for item in undo.into_iter().rev() {
+            let result = match &item {
+                SaveUndo::Inserted(schema_name, primary_key) => server_connection.remove_permanently(schema_name, primary_key).await,
+                SaveUndo::Updated(path, stored) => server_connection.update(path, stored).await,
+                SaveUndo::Removed(path, stored) => server_connection.restore(path, stored).await,
+            };
+
+            if let Err(err) = result {
+                log::warn!(target: LOG_HTTP, "rollback of {:?} failed : {}", item, err);
+                errors.push(err.to_string());
+            }
+        }
+
+        errors
+    }
+
+    // save the parent and the active child row as a unit, undoing the writes already done when one of them fails
+    async fn save_with_dependents(&mut self, server_connection: &mut ServerConnection, child_name: Option<&str>) -> Result<(Value, Vec<DataViewSaveResult>), Box<dyn std::error::Error>> {
+        let mut save_results = vec![];
+        let is_new = self.action == DataViewProcessAction::New;
+        let schema_name = self.data_view_id.schema_name.clone();
+        let form_id = self.data_view_id.form_id.clone();
+        // the stored values of an edited parent are written back if the rows fail
+        let stored = match server_connection.service_map.get(&schema_name) {
+            Some(service) if !is_new => service.get_primary_key(&self.instance).and_then(|primary_key| service.find_one(&primary_key).cloned()),
+            _ => None,
+        };
+
+        let obj_in = match self.save(server_connection).await {
+            Ok(obj_in) => obj_in,
+            Err(err) => {
+                save_results.push(DataViewSaveResult::new(&form_id, None, None, Some(err.to_string())));
+                return Ok((Value::Null, save_results));
+            }
+        };
+
+        let primary_key = server_connection.service_map.get(&schema_name).and_then(|service| service.get_primary_key(&obj_in));
+        save_results.push(DataViewSaveResult::new(&form_id, None, primary_key.clone(), None));
+        let mut undo = vec![];
+
+        if let Some(path) = &self.path {
+            let primary_key = if is_new { primary_key.clone() } else { None };
+            undo.extend(SaveUndo::of_write(&schema_name, path, stored, primary_key));
+        }
+
+        if self.typ == DataViewType::ObjectProperty {
+            if let Some(index) = self.active_index {
+                self.filter_results[index] = obj_in.clone();
+            }
+        }
+
+        // rows of the inline grids of the Dependent childs, that take the key of the parent just saved
+        let parent_primary_key = primary_key.clone().unwrap_or(json!({}));
+        let dependents = server_connection.login_response.openapi.get_dependents(&schema_name, false);
+        let mut saved_rows = vec![];
+        let mut failure = None;
+
+        for child_index in 0..self.childs.len() {
+            let data_view = &mut self.childs[child_index];
+
+            if data_view.typ != DataViewType::Dependent {
+                continue;
+            }
+
+            let Some(path) = data_view.path.clone() else {
+                continue;
+            };
+
+            let child_schema = data_view.data_view_id.schema_name.clone();
+            let child_form_id = data_view.data_view_id.form_id.clone();
+            let mut foreign_keys = vec![];
+
+            for item in dependents.iter().filter(|item| item.schema == child_schema) {
+                if let Some(foreign_key) = server_connection.login_response.openapi.get_foreign_key(&item.schema, &item.field, &parent_primary_key)? {
+                    foreign_keys.push(foreign_key);
+                }
+            }
+
+            // the removed keys are kept until the whole save succeeds, the rollback puts the rows back on the server
+            for primary_key in data_view.removed_rows.clone() {
+                let service = server_connection.service_map.get(&child_schema).context(format!("Missing service {} in service_map", child_schema))?;
+                let stored = service.find_one(&primary_key).cloned();
+                let soft_delete = service.soft_delete.is_some();
+
+                match server_connection.remove(&child_schema, &primary_key).await {
+                    Ok(_) => undo.extend(stored.map(|stored| SaveUndo::of_removal(&path, stored, soft_delete))),
+                    Err(err) => {
+                        save_results.push(DataViewSaveResult::new(&child_form_id, None, Some(primary_key), Some(err.to_string())));
+                        failure.get_or_insert(child_schema.clone());
+                    }
+                }
+            }
+
+            for index in 0..data_view.filter_results.len() {
+                let mut row = data_view.filter_results[index].clone();
+
+                for foreign_key in &foreign_keys {
+                    for (field_name, value) in foreign_key.as_object().context("broken foreign key")? {
+                        row[field_name] = value.clone();
+                    }
+                }
+
+                let service = server_connection.service_map.get(&child_schema).context(format!("Missing service {} in service_map", child_schema))?;
+                let stored = service.get_primary_key(&row).and_then(|primary_key| service.find_one(&primary_key).cloned());
+
+                // rows that keep the stored values aren't sent
+                let result = match &stored {
+                    Some(stored) if stored == &row => continue,
+                    Some(_) => server_connection.update(&path, &row).await,
+                    None => server_connection.save(&path, &row).await,
+                };
+
+                match result {
+                    Ok(obj_in) => {
+                        let row_primary_key = server_connection.service_map.get(&child_schema).and_then(|service| service.get_primary_key(&obj_in));
+                        save_results.push(DataViewSaveResult::new(&child_form_id, Some(index), row_primary_key.clone(), None));
+                        undo.extend(SaveUndo::of_write(&child_schema, &path, stored, row_primary_key));
+                        saved_rows.push((child_index, index, obj_in));
+                    }
+                    Err(err) => {
+                        save_results.push(DataViewSaveResult::new(&child_form_id, Some(index), None, Some(err.to_string())));
+                        failure.get_or_insert(child_schema.clone());
+                    }
+                }
+            }
+        }
+
+        let mut child_obj_in = json!({});
+
+        let failure = match (failure, child_name) {
+            (Some(failure), _) => Some(failure),
+            (None, Some(child_name)) => {
+                let data_view = self
+                    .childs
+                    .iter_mut()
+                    .find(|item| item.data_view_id.schema_name == child_name)
+                    .context(format!("save_with_dependents : Missing item {} in data_view {}", child_name, form_id))?;
+
+                if data_view.path.is_none() {
+                    None
+                } else {
+                    match data_view.save(server_connection).await {
+                        Ok(obj_in) => {
+                            let child_primary_key = server_connection.service_map.get(&data_view.data_view_id.schema_name).and_then(|service| service.get_primary_key(&obj_in));
+                            save_results.push(DataViewSaveResult::new(&data_view.data_view_id.form_id, data_view.active_index, child_primary_key, None));
+                            child_obj_in = obj_in;
+                            None
+                        }
+                        Err(err) => {
+                            save_results.push(DataViewSaveResult::new(&data_view.data_view_id.form_id, data_view.active_index, None, Some(err.to_string())));
+                            Some(child_name.to_string())
+                        }
+                    }
+                }
+            }
+            (None, None) => None,
+        };
+
+        // ObjectProperty rows travel inside the parent instance, they are saved when the parent returns them
+        for data_view in self.childs.iter().filter(|item| item.typ == DataViewType::ObjectProperty) {
+            let stored_rows = obj_in.get(&data_view.data_view_id.schema_name).and_then(|rows| rows.as_array()).map(|rows| rows.len()).unwrap_or(0);
+
+            for index in 0..data_view.filter_results.len() {
+                let error = if let Some(child_schema) = &failure {
+                    Some(format!("rolled back after failure in {}", child_schema))
+                } else if index >= stored_rows {
+                    Some(format!("row {} of {} wasn't returned by the server", index + 1, data_view.data_view_id.schema_name))
+                } else {
+                    None
+                };
+
+                save_results.push(DataViewSaveResult::new(&data_view.data_view_id.form_id, Some(index), None, error));
+            }
+        }
+
+        // the parent is only kept with all of its rows
+        if let Some(child_schema) = failure {
+            let errors = DataView::undo_saves(server_connection, undo).await;
+
+            save_results[0].error = if errors.is_empty() {
+                Some(format!("rolled back after failure in {}", child_schema))
+            } else {
+                Some(format!("failure in {} and rollback also failed : {}", child_schema, errors.join(", ")))
+            };
+
+            return Ok((Value::Null, save_results));
+        }
+
+        for (child_index, index, obj_in) in saved_rows {
+            self.childs[child_index].filter_results[index] = obj_in;
+        }
+
+        for data_view in self.childs.iter_mut().filter(|item| item.typ == DataViewType::Dependent) {
+            data_view.removed_rows.clear();
+        }
+
+        if child_name.is_some() {
+            Ok((child_obj_in, save_results))
         } else {
-            server_connection.update(path, &self.instance).await
+            Ok((obj_in, save_results))
         }
     }
 
@@ -2342,7 +3605,19 @@ trait RemoteListener {
     fn on_notify(schema_name: &str, primary_key: &Value, action: &str);
 }
 
-trait CallbackPartial {}
+#[derive(Serialize, Default, Debug)]
+pub struct LoginProgress {
+    pub schema_name: String,
+    pub index: usize,
+    pub count: usize,
+    pub finished: bool,
+    pub rows: usize,
+    pub bytes: usize,
+}
+
+pub trait CallbackPartial {
+    fn on_progress(&self, progress: &LoginProgress);
+}
 
 #[derive(Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -2364,10 +3639,16 @@ pub struct LoginResponseClient {
     pub openapi: OpenAPI,
 }
 
+const ROLE_METHODS: [&str; 5] = ["get", "post", "patch", "put", "delete"];
+
 #[derive(Default)]
 pub struct ServerConnection {
     http_rest: HttpRestRequest,
     pub login_response: LoginResponseClient,
+    // active group owner, admins (group owner 1) can switch it during the session
+    pub rufs_group_owner: u64,
+    // formats the masked numbers of the cached texts
+    pub locale: Locale,
     service_map: HashMap<String, Service>,
     //pathname: String,
     //remote_listeners: Vec<dyn RemoteListener>,
@@ -2382,9 +3663,52 @@ impl ServerConnection {
         }
     }
 
+    // the role mask has one bit for each method, in the order of ROLE_METHODS
+    pub fn is_allowed(&self, path: &str, method: &str) -> bool {
+        let Some(role) = self.login_response.roles.iter().find(|role| role.path == path) else {
+            return false;
+        };
+
+        let Some(index) = ROLE_METHODS.iter().position(|item| *item == method) else {
+            return false;
+        };
+
+        role.mask & (1 << index) != 0
+    }
+
+    pub fn switch_group_owner(&mut self, rufs_group_owner: u64) -> Result<(), Box<dyn std::error::Error>> {
+        if self.login_response.rufs_group_owner != 1 {
+            return Err(format!("Permission denied : user {} can't switch group owner", self.login_response.name))?;
+        }
+
+        for service in self.service_map.values_mut() {
+            service.filter_group_owner(rufs_group_owner);
+        }
+
+        self.rufs_group_owner = rufs_group_owner;
+        Ok(())
+    }
+
+    pub fn check_access(&self, path: &str, method: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if self.is_allowed(path, method) {
+            Ok(())
+        } else {
+            Err(format!("Permission denied : user {} can't {} in {}", self.login_response.name, method, path))?
+        }
+    }
+
     // ignoreCache is used in websocket notifications
     async fn get(&mut self, schema_name: &str, primary_key: &Value) -> Result<&Value, Box<dyn std::error::Error>> {
         let service = self.service_map.get_mut(schema_name).context(format!("Missing service {} in service_map", schema_name))?;
+
+        if let Some(pos) = service.find_deleted_pos(primary_key) {
+            return Ok(&service.list_deleted[pos]);
+        }
+
+        if let Some(pos) = service.find_hidden_pos(primary_key) {
+            return Ok(&service.list_hidden[pos]);
+        }
+
         let pos = service.find_pos(primary_key);
 
         let pos = if let Some(pos) = pos {
@@ -2448,7 +3772,15 @@ impl ServerConnection {
         Ok(())
     }
 
+    // rows and descriptions found by the text, only for the suggestions, the cache of the service isn't changed
+    async fn search_remote(&self, schema_name: &str, text: &str, limit: usize) -> Result<(Vec<Value>, Vec<String>), Box<dyn std::error::Error>> {
+        let service = self.service_map.get(schema_name).context(format!("Missing service {} in service_map", schema_name))?;
+        let (list, list_str, _) = service.query_remote(self, &json!({"search": text.trim(), "limit": limit})).await?;
+        Ok((list, list_str))
+    }
+
     async fn save(&mut self, path: &str, item_send: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+        self.check_access(path, "post")?;
         let schema_name = &path[1..].to_string().to_case(convert_case::Case::Camel);
         let service = self
             .service_map
@@ -2458,48 +3790,140 @@ impl ServerConnection {
         let method = "post"; //data_view.method
         let data_out = self.login_response.openapi.copy_fields(&service.path, method, &schema_place, false, item_send, false, false, false)?;
         let data = self.http_rest.save(&service.path, &data_out).await?;
+        self.insert_internal(schema_name, &data)?;
+        Ok(data)
+    }
+
+    // posts back a removed row with all of its stored columns, so it keeps the key it had
+    async fn restore(&mut self, path: &str, stored: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+        self.check_access(path, "post")?;
+        let schema_name = &path[1..].to_string().to_case(convert_case::Case::Camel);
+        let service = self.service_map.get(schema_name).context(format!("Missing service {} in service_map", schema_name))?;
+        let primary_key = service.get_primary_key(stored).context(format!("Missing primary key of the removed row of {}", schema_name))?;
+        let data = self.http_rest.save(&service.path, stored).await?;
+
+        if service.get_primary_key(&data).as_ref() != Some(&primary_key) {
+            Err(format!("Row {} of {} was restored with another key", primary_key, schema_name))?;
+        }
+
+        self.insert_internal(schema_name, &data)?;
+        Ok(data)
+    }
+
+    fn insert_internal(&mut self, schema_name: &str, data: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        let service = self.service_map.get_mut(schema_name).context(format!("Missing service {} in service_map", schema_name))?;
         let new_pos = service.update_list(data.clone(), None);
-        self.update_list_str(schema_name, &data, None, new_pos)?;
-        let service = self.service_map.get(schema_name).unwrap();
+        self.update_list_str(schema_name, data, None, new_pos)?;
+        let rufs_group_owner = self.rufs_group_owner;
+        let service = self.service_map.get_mut(schema_name).unwrap();
+        service.hide_row(new_pos, rufs_group_owner);
+
+        if service.list.len() != service.list_str.len() {
+            log::warn!(target: LOG_CACHE, "{} : list.len({}) != list_str.len({})", service.schema_name, service.list.len(), service.list_str.len());
+        }
+
+        Ok(())
+    }
+
+    async fn update(&mut self, path: &str, item_send: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+        self.check_access(path, "put")?;
+        self.send_update(path, item_send).await
+    }
+
+    // the soft delete is an update allowed by the permission of delete
+    async fn send_update(&mut self, path: &str, item_send: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+        let schema_name = &path[1..].to_string().to_case(convert_case::Case::Camel);
+        let service = self.service_map.get_mut(schema_name).unwrap();
+        let schema_place = SchemaPlace::Request; //data_view.schema_place
+        let method = "put"; //data_view.method
+        let data_out = self.login_response.openapi.copy_fields(&service.path, method, &schema_place, false, item_send, false, false, false)?;
+        let primary_key = &service.get_primary_key(&data_out).context(format!("Missing primary key"))?;
+        let data = self.http_rest.update(&service.path, primary_key, &data_out).await?;
+        service.take_deleted(primary_key);
+        service.take_hidden(primary_key);
+        let old_pos = service.find_pos(primary_key);
+        let new_pos = service.update_list(data.clone(), old_pos);
+        self.update_list_str(schema_name, &data, old_pos, new_pos)?;
+        let rufs_group_owner = self.rufs_group_owner;
+        let service = self.service_map.get_mut(schema_name).unwrap();
+        service.hide_row(new_pos, rufs_group_owner);
+        service.filter_deleted();
+
+        if service.list.len() != service.list_str.len() {
+            log::warn!(target: LOG_CACHE, "{} : list.len({}) != list_str.len({})", service.schema_name, service.list.len(), service.list_str.len());
+        }
+
+        Ok(data)
+    }
+
+    // returns the reference of the stored file, a plain string or the field 'url' of the response
+    async fn upload(&self, path: &str, data: Vec<u8>, content_type: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let data_in = self.http_rest.upload(path, data, content_type).await?;
+
+        match &data_in {
+            Value::String(reference) => Ok(reference.clone()),
+            Value::Object(obj) => Ok(obj.get("url").and_then(|url| url.as_str()).context(format!("Missing url in upload response {}", data_in))?.to_string()),
+            _ => Err(format!("Unexpected upload response {}", data_in))?,
+        }
+    }
+
+    async fn remove(&mut self, schema_name: &str, primary_key: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+        let path = self.service_map.get(schema_name).context(format!("Missing service {} in service_map", schema_name))?.path.clone();
+        self.check_access(&path, "delete")?;
+        let service = self.service_map.get_mut(schema_name).context(format!("Missing service {} in service_map", schema_name))?;
+
+        // x-softDelete only marks the row, that goes to the trash and can be restored
+        if let Some(column) = service.soft_delete.clone() {
+            let mut obj = service.find_one(primary_key).cloned().context(format!("Missing {} in {}", primary_key, schema_name))?;
+            obj[&column] = json!(Utc::now().to_rfc3339());
+            return self.send_update(&path, &obj).await;
+        }
+
+        self.remove_permanently(schema_name, primary_key).await
+    }
+
+    // removes the rows in order, stopping at the first failure, when the rows already removed are put back,
+    // so a record is never kept without the rows that cascaded from it
+    async fn remove_rows(&mut self, form_id: &str, removals: Vec<(String, Value)>) -> Result<Vec<DataViewSaveResult>, Box<dyn std::error::Error>> {
+        let mut results = vec![];
+        let mut undo = vec![];
+
+        for (schema_name, primary_key) in removals {
+            let service = self.service_map.get(&schema_name).context(format!("Missing service {} in service_map", schema_name))?;
+            let stored = service.find_one(&primary_key).cloned();
+            let path = service.path.clone();
+            let soft_delete = service.soft_delete.is_some();
+
+            let err = match self.remove(&schema_name, &primary_key).await {
+                Ok(_) => {
+                    undo.extend(stored.map(|stored| SaveUndo::of_removal(&path, stored, soft_delete)));
+                    results.push(DataViewSaveResult::new(form_id, None, Some(primary_key), None));
+                    continue;
+                }
+                Err(err) => err,
+            };
 
-        if service.list.len() != service.list_str.len() {
-            println!(
-                "[DEBUG - {} - service.list.len({}) != service.list_str.len({})]",
-                service.schema_name,
-                service.list.len(),
-                service.list_str.len()
-            );
-        }
+            let errors = DataView::undo_saves(self, std::mem::take(&mut undo)).await;
 
-        Ok(data)
-    }
+            let rollback = if errors.is_empty() {
+                format!("rolled back after failure in {} {}", schema_name, primary_key)
+            } else {
+                format!("failure in {} {} and rollback also failed : {}", schema_name, primary_key, errors.join(", "))
+            };
 
-    async fn update(&mut self, path: &str, item_send: &Value) -> Result<Value, Box<dyn std::error::Error>> {
-        let schema_name = &path[1..].to_string().to_case(convert_case::Case::Camel);
-        let service = self.service_map.get_mut(schema_name).unwrap();
-        let schema_place = SchemaPlace::Request; //data_view.schema_place
-        let method = "put"; //data_view.method
-        let data_out = self.login_response.openapi.copy_fields(&service.path, method, &schema_place, false, item_send, false, false, false)?;
-        let primary_key = &service.get_primary_key(&data_out).context(format!("Missing primary key"))?;
-        let data = self.http_rest.update(&service.path, primary_key, &data_out).await?;
-        let old_pos = service.find_pos(primary_key);
-        let new_pos = service.update_list(data.clone(), old_pos);
-        self.update_list_str(schema_name, &data, old_pos, new_pos)?;
-        let service = self.service_map.get(schema_name).unwrap();
+            for result in &mut results {
+                result.error = Some(rollback.clone());
+            }
 
-        if service.list.len() != service.list_str.len() {
-            println!(
-                "[DEBUG - {} - service.list.len({}) != service.list_str.len({})]",
-                service.schema_name,
-                service.list.len(),
-                service.list_str.len()
-            );
+            results.push(DataViewSaveResult::new(form_id, None, Some(primary_key.clone()), Some(format!("{} {} : {}", schema_name, primary_key, err))));
+            break;
         }
 
-        Ok(data)
+        Ok(results)
     }
 
-    async fn remove(&mut self, schema_name: &str, primary_key: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+    // removes the row even when the schema has x-softDelete, a rolled back insert mustn't be left in the trash
+    async fn remove_permanently(&mut self, schema_name: &str, primary_key: &Value) -> Result<Value, Box<dyn std::error::Error>> {
         let service = self.service_map.get_mut(schema_name).context(format!("Missing service {} in service_map", schema_name))?;
         let old_value = self.http_rest.remove(&service.path, primary_key).await?;
         //#[cfg(test)]
@@ -2720,11 +4144,12 @@ impl ServerConnection {
         */
     }
     // public
-    pub async fn login(&mut self, login_path: &str, username: &str, password: &str /*, callback_partial: CallbackPartial*/) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn login(&mut self, login_path: &str, username: &str, password: &str, callback_partial: Option<&dyn CallbackPartial>) -> Result<(), Box<dyn std::error::Error>> {
         self.service_map.clear();
         let password = md5::compute(password);
         let password = format!("{:x}", password);
         self.login_response = self.http_rest.login(login_path, username, &password).await?;
+        self.rufs_group_owner = self.login_response.rufs_group_owner;
         let mut list_dependencies = vec![];
         // depois carrega os serviços autorizados
         for role in self.login_response.roles.clone() {
@@ -2761,21 +4186,40 @@ impl ServerConnection {
 
         //    		if user == "admin") listDependencies = ["rufsUser", "rufsGroupOwner", "rufsGroup", "rufsGroupUser"];
 
-        for schema_name in list_dependencies {
-            //console.log(`login ${schemaName}`)
+        let count = list_dependencies.len();
+
+        for (index, schema_name) in list_dependencies.into_iter().enumerate() {
             let service = self.service_map.get(&schema_name);
 
             if let Some(service) = service {
-                let (list, list_str) = service.query_remote(self, &Value::Null).await?;
+                if let Some(callback_partial) = callback_partial {
+                    callback_partial.on_progress(&LoginProgress {
+                        schema_name: schema_name.clone(),
+                        index,
+                        count,
+                        ..Default::default()
+                    });
+                }
+
+                let (list, list_str, bytes) = service.query_remote(self, &Value::Null).await?;
 
                 if list.len() != list_str.len() {
-                    println!("[DEBUG - {} - list.len({}) != list_str.len({})]", schema_name, list.len(), list_str.len());
+                    log::warn!(target: LOG_CACHE, "login({}) : list.len({}) != list_str.len({})", schema_name, list.len(), list_str.len());
+                }
+
+                if let Some(callback_partial) = callback_partial {
+                    callback_partial.on_progress(&LoginProgress {
+                        schema_name: schema_name.clone(),
+                        index,
+                        count,
+                        finished: true,
+                        rows: list.len(),
+                        bytes,
+                    });
                 }
 
                 let service = self.service_map.get_mut(&schema_name).unwrap();
-                service.list = list;
-                println!("login 1.1 : service {}, list_str.len = {}", schema_name, list_str.len());
-                service.list_str = list_str;
+                service.set_list(list, list_str);
             }
         }
 
@@ -2813,11 +4257,80 @@ pub trait DataViewWatch: std::marker::Sync + Send {
     fn menu(&self) -> Value;
 }
 
+// field waiting for a record of the referenced form, with the state of its form when it was left
+struct ReferenceOrigin {
+    element_id: HtmlElementId,
+    action: DataViewProcessAction,
+    instance: Value,
+    // opened by reference-search, the rows of the list can be selected
+    select: bool,
+}
+
+// bulk operation over the selected rows, sent in batches to report the progress
+struct BulkOperation {
+    // remove or update
+    operation: String,
+    field_name: Option<String>,
+    value: Value,
+    primary_keys: Vec<Value>,
+    results: Vec<DataViewSaveResult>,
+}
+
+// writes done by save_with_dependents, undone when a later one fails
+#[derive(Debug)]
+enum SaveUndo {
+    // schema and primary key of an inserted row
+    Inserted(String, Value),
+    // path and stored values of an updated row
+    Updated(String, Value),
+    // path and stored values of a removed row
+    Removed(String, Value),
+}
+
+impl SaveUndo {
+    // the row was stored before the write, or got primary_key when it was inserted
+    fn of_write(schema_name: &str, path: &str, stored: Option<Value>, primary_key: Option<Value>) -> Option<SaveUndo> {
+        match (stored, primary_key) {
+            (Some(stored), _) => Some(SaveUndo::Updated(path.to_string(), stored)),
+            (None, Some(primary_key)) => Some(SaveUndo::Inserted(schema_name.to_string(), primary_key)),
+            (None, None) => None,
+        }
+    }
+
+    // a soft delete only set the marker of the row, that is written back
+    fn of_removal(path: &str, stored: Value, soft_delete: bool) -> SaveUndo {
+        if soft_delete {
+            SaveUndo::Updated(path.to_string(), stored)
+        } else {
+            SaveUndo::Removed(path.to_string(), stored)
+        }
+    }
+}
+
+// form visited by the user, with the state it had when it was left
+struct NavigationEntry {
+    form_id: String,
+    primary_key: Value,
+    hash: String,
+    label: String,
+    action: DataViewProcessAction,
+    instance: Value,
+}
+
 //#[derive(Default)]
 pub struct DataViewManager<'a> {
     pub server_connection: ServerConnection,
     data_view_map: HashMap<String, DataView>,
     watcher: &'a Box<dyn DataViewWatch>,
+    renderer: Box<dyn DataViewRenderer>,
+    locale: Locale,
+    // field that opened a referenced form by reference-create or reference-search, by form_id of the referenced form
+    reference_origins: HashMap<String, ReferenceOrigin>,
+    // stack of the visited forms, with the entries after navigation_index reachable by forward
+    navigation: Vec<NavigationEntry>,
+    navigation_index: usize,
+    // bulk operations in progress, by form_id
+    bulk_operations: HashMap<String, BulkOperation>,
 }
 
 #[macro_export]
@@ -2882,7 +4395,7 @@ macro_rules! data_view_get_mut {
         };
 
         let func_name = function!();
-        println!("[{} - data_view_get_mut] : {:?}", func_name, $element_id);
+        log::trace!(target: LOG_EVENT, "[{} - data_view_get_mut] : {:?}", func_name, $element_id);
         data_view
     }};
 }
@@ -2891,7 +4404,15 @@ macro_rules! data_view_get_mut {
 macro_rules! data_view_get_parent_mut {
     ($data_view_manager:tt, $element_id:tt) => {{
         let data_view = $data_view_manager.data_view_map.get_mut(&$element_id.data_view_id.form_id_parent).context(format!("Missing parent schema {} in data_view_manager", $element_id.data_view_id.form_id_parent))?;
-        println!("[data_view_get_parent_mut] : {:?}", $element_id);
+        log::trace!(target: LOG_EVENT, "[data_view_get_parent_mut] : {:?}", $element_id);
+        data_view
+    }};
+}
+
+#[macro_export]
+macro_rules! data_view_get_parent {
+    ($data_view_manager:tt, $element_id:tt) => {{
+        let data_view = $data_view_manager.data_view_map.get(&$element_id.data_view_id.form_id_parent).context(format!("Missing parent schema {} in data_view_manager", $element_id.data_view_id.form_id_parent))?;
         data_view
     }};
 }
@@ -2903,46 +4424,455 @@ impl DataViewManager<'_> {
             server_connection,
             data_view_map: Default::default(),
             watcher,
+            renderer: Box::new(BootstrapRenderer::default()),
+            locale: Locale::default(),
+            reference_origins: Default::default(),
+            navigation: vec![],
+            navigation_index: 0,
+            bulk_operations: Default::default(),
+        }
+    }
+
+    // rows of the cache that reference the record through the dependent relations, the relations declared with
+    // x-onDelete: cascade in the foreign key have their rows removed first, the others restrict the delete
+    fn delete_dependents(&self, schema_name: &str, primary_key: &Value, counts: &mut Vec<(String, usize)>, removals: &mut Vec<(String, Value)>) -> Result<(), Box<dyn std::error::Error>> {
+        let openapi = &self.server_connection.login_response.openapi;
+
+        // a soft delete keeps the record, so its dependents stay as they are and still reference it when it is restored
+        if self.server_connection.service_map.get(schema_name).is_some_and(|service| service.soft_delete.is_some()) {
+            return Ok(());
+        }
+
+        for item in openapi.get_dependents(schema_name, false) {
+            let Some(foreign_key) = openapi.get_foreign_key(&item.schema, &item.field, primary_key)? else {
+                continue;
+            };
+
+            // rows that can't be seen can't be cascaded, so they restrict the removal
+            let Some(service) = self.server_connection.service_map.get(&item.schema).filter(|service| service.loaded) else {
+                return Err(format!("The rows of {} that reference {} {} can't be checked", item.schema, schema_name, primary_key).into());
+            };
+
+            let rows = service.find(&foreign_key);
+
+            if rows.is_empty() {
+                continue;
+            }
+
+            let on_delete = openapi
+                .get_property(&item.schema, &item.field)
+                .and_then(|field| field.schema_data.extensions.get("x-onDelete"))
+                .and_then(|value| value.as_str())
+                .unwrap_or("restrict");
+
+            if on_delete != "cascade" {
+                Err(format!("{} rows of {} reference {} {}", rows.len(), item.schema, schema_name, primary_key))?;
+            }
+
+            // the cascade would only send them to the trash, still referencing the removed record
+            if service.soft_delete.is_some() {
+                Err(format!("{} rows of {} can't be removed with {} {}, they are only soft deleted", rows.len(), item.schema, schema_name, primary_key))?;
+            }
+
+            match counts.iter_mut().find(|(schema, _)| schema == &item.schema) {
+                Some((_, count)) => *count += rows.len(),
+                None => counts.push((item.schema.clone(), rows.len())),
+            }
+
+            for row in rows {
+                let Some(row_key) = service.get_primary_key(row) else {
+                    continue;
+                };
+
+                if removals.iter().any(|(schema, key)| schema == &item.schema && key == &row_key) {
+                    continue;
+                }
+
+                // the row is registered before its own dependents to stop on cycles, and moved after them to be removed later
+                let pos = removals.len();
+                removals.push((item.schema.clone(), row_key.clone()));
+                self.delete_dependents(&item.schema, &row_key, counts, removals)?;
+                let removal = removals.remove(pos);
+                removals.push(removal);
+            }
+        }
+
+        Ok(())
+    }
+
+    // text of the confirmation of a removal, with the count of the dependent rows removed with it
+    fn delete_confirm_message(&self, counts: &[(String, usize)]) -> String {
+        let openapi = &self.server_connection.login_response.openapi;
+        let mut lines = vec![self.locale.translate("delete_confirm")];
+
+        if !counts.is_empty() {
+            lines.push(format!("{} :", self.locale.translate("delete_dependents")));
+        }
+
+        for (schema, count) in counts {
+            let label = match openapi.components.as_ref().and_then(|components| components.schemas.get(schema)) {
+                Some(ReferenceOr::Item(item)) => DataView::label_from_schema_data(&item.schema_data, schema, &self.locale),
+                _ => schema.clone(),
+            };
+
+            lines.push(format!("{} : {}", label, count));
+        }
+
+        lines.join("\n")
+    }
+
+    // removes the cascading dependents and then the record, see ServerConnection::remove_rows
+    async fn remove_with_dependents(&mut self, form_id: &str, schema_name: &str, primary_key: &Value) -> Result<Vec<DataViewSaveResult>, Box<dyn std::error::Error>> {
+        let mut counts = vec![];
+        let mut removals = vec![];
+        self.delete_dependents(schema_name, primary_key, &mut counts, &mut removals)?;
+        removals.push((schema_name.to_string(), primary_key.clone()));
+        self.server_connection.remove_rows(form_id, removals).await
+    }
+
+    // sends the next batch of the bulk operation, the results are returned when all the rows were sent
+    async fn bulk_step(&mut self, element_id: &HtmlElementId) -> Result<DataViewResponse, Box<dyn std::error::Error>> {
+        let form_id = element_id.data_view_id.form_id.clone();
+        let mut bulk = self.bulk_operations.remove(&form_id).context(format!("Missing bulk operation in {}", form_id))?;
+        let data_view = data_view_get!(self, element_id);
+        let schema_name = data_view.data_view_id.schema_name.clone();
+        let path = data_view.path.clone().context(format!("Missing path of {}", form_id))?;
+        let end = (bulk.results.len() + BULK_BATCH).min(bulk.primary_keys.len());
+        // each updated row is edited apart, with the rules and expressions of the form
+        let mut row_view = DataView::new(&path, DataViewType::Primary, None, DataViewProcessAction::Edit);
+
+        if bulk.operation == "update" {
+            row_view.set_schema(&self.server_connection)?;
+        }
+
+        for index in bulk.results.len()..end {
+            let primary_key = bulk.primary_keys[index].clone();
+
+            let result = match (bulk.operation.as_str(), &bulk.field_name) {
+                ("update", Some(field_name)) => {
+                    let service = self.server_connection.service_map.get(&schema_name).context(format!("Missing service {} in service_map", schema_name))?;
+
+                    match service.find_one(&primary_key).cloned() {
+                        Some(obj) => match row_view.bulk_update_row(&self.server_connection, self.watcher, &obj, field_name, &bulk.value) {
+                            Ok(obj) => self.server_connection.update(&path, &obj).await,
+                            Err(err) => Err(err),
+                        },
+                        None => Err(format!("Missing row in {}", schema_name).into()),
+                    }
+                }
+                _ => match self.remove_with_dependents(&form_id, &schema_name, &primary_key).await {
+                    // the failure stops the removal, so it is the last result
+                    Ok(results) => match results.into_iter().last().and_then(|result| result.error) {
+                        Some(error) => Err(error.into()),
+                        None => Ok(Value::Null),
+                    },
+                    Err(err) => Err(err),
+                },
+            };
+
+            let error = result.err().map(|err| format!("{} : {}", primary_key, err));
+            bulk.results.push(DataViewSaveResult::new(&form_id, Some(index), Some(primary_key), error));
+        }
+
+        let count = bulk.primary_keys.len();
+        let finished = bulk.results.len() >= count;
+        let failed = bulk.results.iter().filter(|result| result.error.is_some()).count();
+        let summary = format!("{} {}, {} {}", bulk.results.len() - failed, self.locale.translate("bulk_succeeded"), failed, self.locale.translate("bulk_failed"));
+        let mut data_view_response = DataViewResponse { form_id: form_id.clone(), ..Default::default() };
+        data_view_response.bulk = json!({"form_id": form_id, "operation": bulk.operation, "index": bulk.results.len(), "count": count, "finished": finished, "summary": summary});
+
+        if !finished {
+            self.bulk_operations.insert(form_id, bulk);
+            return Ok(data_view_response);
+        }
+
+        let service = self.server_connection.service_map.get(&schema_name).context(format!("Missing service {} in service_map", schema_name))?;
+        let data_view = data_view_get_mut!(self, element_id);
+        // the rows that failed stay selected, to be tried again
+        data_view.selected_rows = bulk.results.iter().filter(|result| result.error.is_some()).filter_map(|result| result.primary_key.clone()).collect();
+        // the filtered rows follow the cache, that already has the removed and updated ones
+        data_view.filter_results = data_view
+            .filter_results
+            .iter()
+            .filter_map(|item| service.get_primary_key(item).and_then(|primary_key| service.find_one(&primary_key).cloned()))
+            .collect();
+        data_view_response.save_results = bulk.results;
+        let data_view = data_view_get!(self, element_id);
+        let params_search = DataViewProcessParams { ..Default::default() };
+        data_view_response.tables = json!({});
+        let table = DataView::build_table(self, data_view, &params_search)?;
+        data_view_response.tables[&data_view.data_view_id.form_id] = json!(table);
+        Ok(data_view_response)
+    }
+
+    // keeps the state of the current form, before it is left or taken by another action
+    fn navigation_save(&mut self) {
+        if let Some(entry) = self.navigation.get_mut(self.navigation_index) {
+            if let Some(data_view) = self.data_view_map.get(&entry.form_id) {
+                entry.action = data_view.action;
+                entry.instance = data_view.instance.clone();
+            }
+        }
+    }
+
+    // the form keeps its entry when it goes on with the same record, like after save, other forms and records drop the forward entries
+    fn navigation_push(&mut self, form_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let data_view = self.data_view_map.get(form_id).context(format!("Missing form {} in data_view_manager", form_id))?;
+
+        let primary_key = match data_view.action {
+            DataViewProcessAction::Edit | DataViewProcessAction::View => data_view.active_primary_key.clone().unwrap_or(json!({})),
+            _ => json!({}),
+        };
+
+        let action = data_view.action.to_string();
+        let label = DataView::label_from_schema_data(&data_view.schema_data, &data_view.data_view_id.schema_name, &self.locale);
+
+        let entry = NavigationEntry {
+            form_id: form_id.to_string(),
+            primary_key: primary_key.clone(),
+            hash: DataView::build_location_hash(form_id, &action, &primary_key)?,
+            label: format!("{} - {}", label, self.locale.translate(&action)),
+            action: data_view.action,
+            instance: data_view.instance.clone(),
+        };
+
+        let is_same_record = |current: &NavigationEntry| current.form_id == form_id && (current.primary_key == entry.primary_key || current.action == DataViewProcessAction::New);
+
+        if self.navigation.get(self.navigation_index).is_some_and(is_same_record) {
+            self.navigation[self.navigation_index] = entry;
+        } else {
+            self.navigation.truncate(self.navigation_index + 1);
+            self.navigation.push(entry);
+            self.navigation_index = self.navigation.len() - 1;
+        }
+
+        Ok(())
+    }
+
+    // the table of the inline grid and the values of the parent that follow its rows
+    fn build_grid_response(&mut self, element_id: &HtmlElementId) -> Result<DataViewResponse, Box<dyn std::error::Error>> {
+        let mut data_view_response = DataViewResponse { changes: json!({}), tables: json!({}), ..Default::default() };
+        let data_view_parent = data_view_get_parent_mut!(self, element_id);
+        data_view_parent.build_changes(element_id, &mut data_view_response.changes)?;
+        data_view_parent.build_states(&mut data_view_response.states);
+        let data_view = data_view_get!(self, element_id);
+        let params_search = DataViewProcessParams { ..Default::default() };
+        let table = DataView::build_table(self, data_view, &params_search)?;
+        data_view_response.tables[&data_view.data_view_id.form_id] = json!(table);
+        Ok(data_view_response)
+    }
+
+    // back to the last entry of form_id, dropping the ones opened from it
+    fn navigation_return(&mut self, form_id: &str) {
+        let end = self.navigation.len().min(self.navigation_index + 1);
+
+        if let Some(index) = self.navigation[..end].iter().rposition(|entry| entry.form_id == form_id) {
+            self.navigation.truncate(index + 1);
+            self.navigation_index = index;
+        }
+    }
+
+    fn build_navigation(&self) -> Value {
+        let Some(entry) = self.navigation.get(self.navigation_index) else {
+            return Value::Null;
+        };
+
+        let items = self.navigation[..=self.navigation_index]
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| NavigationItemView { index, label: entry.label.clone(), active: index == self.navigation_index })
+            .collect();
+
+        let view = NavigationView {
+            items,
+            back: if self.navigation_index > 0 { Some(self.locale.translate("back")) } else { None },
+            forward: if self.navigation_index + 1 < self.navigation.len() { Some(self.locale.translate("forward")) } else { None },
+        };
+
+        json!({"index": self.navigation_index, "form_id": entry.form_id, "hash": entry.hash, "html": self.renderer.render_navigation(&view)})
+    }
+
+    // restores the form of the entry with the state it had when it was left
+    fn navigation_go(&mut self, index: usize) -> Result<DataViewResponse, Box<dyn std::error::Error>> {
+        if index >= self.navigation.len() {
+            Err(format!("Missing navigation entry {}", index))?;
+        }
+
+        self.navigation_save();
+        // the fields waiting for a referenced form are left behind
+        self.reference_origins.clear();
+        self.navigation_index = index;
+        let entry = &self.navigation[index];
+        let (form_id, action, instance) = (entry.form_id.clone(), entry.action, entry.instance.clone());
+        let data_view = self.data_view_map.get_mut(&form_id).context(format!("Missing form {} in data_view_manager", form_id))?;
+        let element_id = &HtmlElementId::new(data_view.data_view_id.schema_name.clone(), None, FormType::Instance, None, None, None);
+
+        if data_view.action != action {
+            data_view.action = action;
+            data_view.set_schema(&self.server_connection)?;
+        }
+
+        // the values are sent again as changes of the rebuilt form
+        if action != DataViewProcessAction::Search {
+            data_view.clear();
+            data_view.set_values(&self.server_connection, self.watcher, &instance, element_id)?;
+        }
+
+        let mut data_view_response = DataViewResponse { form_id, changes: json!({}), tables: json!({}), ..Default::default() };
+        let data_view = data_view_get!(self, element_id);
+        data_view_response.html = DataView::build_form(self, data_view, FormType::Instance)?;
+        let params_search = DataViewProcessParams { ..Default::default() };
+
+        for data_view in std::iter::once(data_view).chain(data_view.childs.iter()) {
+            let table = DataView::build_table(self, data_view, &params_search)?;
+            data_view_response.tables[&data_view.data_view_id.form_id] = json!(table);
+        }
+
+        data_view_response.lists = DataView::build_lists(self, data_view)?;
+        data_view_response.options = DataView::build_options(self, data_view)?;
+        let data_view = data_view_get_mut!(self, element_id);
+        data_view.build_changes(element_id, &mut data_view_response.changes)?;
+        data_view.build_states(&mut data_view_response.states);
+        data_view_response.navigation = self.build_navigation();
+        Ok(data_view_response)
+    }
+
+    // sets in the origin field the foreign key of obj, a record of the referenced schema
+    fn return_reference(&mut self, origin: &ReferenceOrigin, obj: &Value, data_view_response: &mut DataViewResponse) -> Result<(), Box<dyn std::error::Error>> {
+        let element_id = &origin.element_id;
+        // the referenced form, in data_view_response.form_id, took the place of the origin when both are of the same schema
+        let is_restored = data_view_response.form_id == element_id.data_view_id.form_id_parent;
+
+        if is_restored {
+            let data_view_parent = data_view_get_parent_mut!(self, element_id);
+            data_view_parent.action = origin.action.clone();
+            data_view_parent.set_schema(&self.server_connection)?;
+            data_view_parent.set_values(&self.server_connection, self.watcher, &origin.instance, element_id)?;
+        }
+
+        let field_name = element_id.field_name.clone().context("Missing field_name")?;
+        let data_view = data_view_get!(self, element_id);
+        let foreign_key = self
+            .server_connection
+            .login_response
+            .openapi
+            .get_foreign_key(&data_view.data_view_id.schema_name, &field_name, obj)?
+            .context(format!("Missing foreign key of {} in {}", field_name, obj))?;
+        let value = foreign_key.get(&field_name).context(format!("Missing field {} in foreign key", field_name))?.clone();
+        // the options were built before the record existed
+        let data_view = data_view_get_mut!(self, element_id);
+        data_view.build_field_filter_results(&self.server_connection)?;
+        let data_view_parent = data_view_get_parent_mut!(self, element_id);
+        data_view_parent.set_value(&self.server_connection, self.watcher.as_ref(), &field_name, &value, element_id)?;
+        data_view_parent.build_changes(element_id, &mut data_view_response.changes)?;
+        let data_view = data_view_get!(self, element_id);
+        let form = DataView::build_form_view(self, data_view, FormType::Instance)?;
+
+        if let Some(field) = form.fields.iter().find(|field| field.name == field_name) {
+            data_view_response.options[&field.id] = json!(self.renderer.render_field_control(field));
+        }
+
+        let data_view_parent = data_view_get_parent!(self, element_id);
+
+        if is_restored {
+            let params_search = DataViewProcessParams { ..Default::default() };
+            data_view_response.form_id = data_view_parent.data_view_id.form_id.clone();
+            data_view_response.html = DataView::build_form(self, data_view_parent, FormType::Instance)?;
+            data_view_response.tables = json!({});
+
+            for data_view in std::iter::once(data_view_parent).chain(data_view_parent.childs.iter()) {
+                let table = DataView::build_table(self, data_view, &params_search)?;
+                data_view_response.tables[&data_view.data_view_id.form_id] = json!(table);
+            }
+        } else {
+            data_view_response.closed.push(data_view_response.form_id.clone());
+            data_view_response.form_id = data_view_parent.data_view_id.form_id.clone();
+            data_view_response.html = "".to_string();
         }
+
+        self.navigation_return(&element_id.data_view_id.form_id_parent);
+        data_view_response.navigation = self.build_navigation();
+        Ok(())
+    }
+
+    pub fn set_renderer(&mut self, renderer: Box<dyn DataViewRenderer>) {
+        self.renderer = renderer;
     }
 
-    pub async fn login(&mut self, params: Value) -> Result<Value, Box<dyn std::error::Error>> {
+    pub async fn login(&mut self, params: Value, callback_partial: Option<&dyn CallbackPartial>) -> Result<Value, Box<dyn std::error::Error>> {
         #[derive(Deserialize)]
         struct LoginDataIn {
             path: String,
             user: String,
             password: String,
+            #[serde(default)]
+            locale: Locale,
         }
 
         let data_in = serde_json::from_value::<LoginDataIn>(params)?;
-        self.server_connection.login(&data_in.path, &data_in.user, &data_in.password).await?;
-        Ok(json!({"menu": self.watcher.menu(), "path": self.server_connection.login_response.path, "jwt_header": self.server_connection.login_response.jwt_header}))
+        self.locale = data_in.locale;
+        self.server_connection.locale = data_in.locale;
+        self.server_connection.login(&data_in.path, &data_in.user, &data_in.password, callback_partial).await?;
+        let mut group_owners = vec![];
+
+        if self.server_connection.login_response.rufs_group_owner == 1 {
+            if let Some(service) = self.server_connection.service_map.get("rufsGroupOwner") {
+                for (item, str) in service.list.iter().zip(service.list_str.iter()) {
+                    group_owners.push(json!({"id": item.get("id"), "name": str}));
+                }
+            }
+        }
+
+        Ok(json!({
+            "menu": self.watcher.menu(),
+            "path": self.server_connection.login_response.path,
+            "jwt_header": self.server_connection.login_response.jwt_header,
+            "rufs_group_owner": self.server_connection.rufs_group_owner,
+            "group_owners": group_owners,
+            "locale": self.locale,
+            "labels": self.locale.labels()
+        }))
+    }
+
+    pub fn switch_group_owner(&mut self, rufs_group_owner: u64) -> Result<Value, Box<dyn std::error::Error>> {
+        self.server_connection.switch_group_owner(rufs_group_owner)?;
+        // forms are rebuilt with the new default and the filtered lists
+        self.data_view_map.clear();
+        self.navigation.clear();
+        self.navigation_index = 0;
+        // the pending references and bulk operations point to the dropped forms
+        self.reference_origins.clear();
+        self.bulk_operations.clear();
+        Ok(json!({"rufs_group_owner": rufs_group_owner}))
     }
 
     async fn process_data_view_action(&mut self, element_id: &HtmlElementId, action: &DataViewProcessAction, params_search: &DataViewProcessParams, params_extra: &Value) -> Result<DataViewResponse, Box<dyn std::error::Error>> {
         fn set_filter_range(data_view: &mut DataView, field_name: &str, range: &str) {
-            let period_labels = [" minuto ", " hora ", " dia ", " semana ", " quinzena ", " mês ", " ano "];
-            let periods = [60, 3600, 86400, 7 * 86400, 15 * 86400, 30 * 86400, 365 * 86400];
-            let mut period = 1;
-
-            for i in 0..period_labels.len() {
-                if range.contains(period_labels[i]) {
-                    period = periods[i] * 1000;
-                    break;
-                }
-            }
+            let range = i18n::range_key(range).unwrap_or(range);
+            let (period_name, kind) = range.split_once('_').unwrap_or((range, ""));
+
+            let period = match period_name {
+                "minute" => 60,
+                "hour" => 3600,
+                "day" => 86400,
+                "week" => 7 * 86400,
+                "fortnight" => 15 * 86400,
+                "month" => 30 * 86400,
+                "year" => 365 * 86400,
+                _ => 1,
+            };
 
             let now = chrono::Local::now();
             let now_period_trunc = (now.timestamp() / period) * period;
             let mut date_end = Local.timestamp_opt(now_period_trunc + period, 0).unwrap();
 
-            let date_ini = if range.contains(" corrente ") {
-                Local.timestamp_opt(now_period_trunc, 0).unwrap()
-            } else if range.contains(" anterior ") {
-                date_end = Local.timestamp_opt(now_period_trunc, 0).unwrap();
-                Local.timestamp_opt(now_period_trunc - period, 0).unwrap()
-            } else {
-                Local.timestamp_opt(now.timestamp() - period, 0).unwrap()
+            let date_ini = match kind {
+                "current" => Local.timestamp_opt(now_period_trunc, 0).unwrap(),
+                "previous" => {
+                    date_end = Local.timestamp_opt(now_period_trunc, 0).unwrap();
+                    Local.timestamp_opt(now_period_trunc - period, 0).unwrap()
+                }
+                _ => Local.timestamp_opt(now.timestamp() - period, 0).unwrap(),
             };
 
             let now_date = Local.with_ymd_and_hms(now.year(), now.month(), now.day(), 0, 0, 0).unwrap();
@@ -2956,11 +4886,11 @@ impl DataViewManager<'_> {
             let year_last_start = Local.with_ymd_and_hms(now.year() - 1, 1, 1, 0, 0, 0).unwrap();
 
             let (date_ini, date_end) = match range {
-                "dia corrente" => (day_active_start, day_active_start.checked_add_days(Days::new(1)).unwrap()),
-                "dia anterior" => (day_last_start, day_active_start),
-                "semana corrente" => (week_active_start, week_active_start.checked_add_days(Days::new(7)).unwrap()),
-                "semana anterior" => (week_last_start, week_active_start),
-                "quinzena corrente" => {
+                "day_current" => (day_active_start, day_active_start.checked_add_days(Days::new(1)).unwrap()),
+                "day_previous" => (day_last_start, day_active_start),
+                "week_current" => (week_active_start, week_active_start.checked_add_days(Days::new(7)).unwrap()),
+                "week_previous" => (week_last_start, week_active_start),
+                "fortnight_current" => {
                     let date_ini = if now.day() <= 15 {
                         month_active_start
                     } else {
@@ -2969,108 +4899,26 @@ impl DataViewManager<'_> {
 
                     (date_ini, date_ini.checked_add_days(Days::new(15)).unwrap())
                 }
-                "quinzena anterior" => {
+                "fortnight_previous" => {
                     let date_end = if now.day() <= 15 {
                         month_active_start
                     } else {
                         Local.with_ymd_and_hms(now.year(), now.month(), 15, 0, 0, 0).unwrap()
                     };
 
-                    let date_ini = if date_end.day() > 15 { date_end.with_day(15).unwrap() } else { date_end.with_day(1).unwrap() };
-
-                    (date_ini, date_end)
-                }
-                "mês corrente" => (month_active_start, month_active_start.checked_add_months(Months::new(1)).unwrap()),
-                "mês anterior" => (month_last_start, month_active_start),
-                "ano corrente" => (year_active_start, year_active_start.checked_add_months(Months::new(12)).unwrap()),
-                "ano anterior" => (year_last_start, year_active_start),
-                _ => (date_ini, date_end),
-            };
-
-            data_view.instance_filter_range_min[field_name] = json!(date_ini.to_rfc3339());
-            data_view.instance_filter_range_max[field_name] = json!(date_end.to_rfc3339());
-        }
-
-        fn build_field_filter_results(data_view: &mut DataView, server_connection: &ServerConnection) -> Result<(), Box<dyn std::error::Error>> {
-            // faz uma referencia local a field.filter_results_str, para permitir opção filtrada, sem alterar a referencia global
-            for (field_name, field) in &data_view.properties {
-                let field = field.as_item().unwrap();
-                let extensions = &field.schema_data.extensions;
-
-                let (list, list_str) = if let Some(reference) = extensions.get("x-$ref") {
-                    let reference = reference.as_str().context("reference is not string")?;
-
-                    if let Some(_service_ref) = server_connection.service_map.get(reference) {
-                        //data_view.serverConnection.getDocuments(service_ref, service.list).await;
-                    }
-
-                    let service = server_connection.service_map.get(&data_view.data_view_id.schema_name).context(format!(
-                        "[build_field_filter_results] Missing service {} in server_connection.service_map.",
-                        data_view.data_view_id.schema_name
-                    ))?;
-
-                    if let Some(service) = server_connection.get_foreign_service(service, field_name, true) {
-                        let mut filter = if let Some(filter) = data_view.field_filter_results.get(field_name) {
-                            filter.clone()
-                        } else {
-                            json!({})
-                        };
-
-                        if filter.as_object().context("filter is not object")?.is_empty() {
-                            if let Some(pos) = reference.chars().position(|c| c == '?') {
-                                let primary_key = queryst::parse(&reference[pos..]).unwrap();
-
-                                for (field_name, value) in primary_key.as_object().unwrap() {
-                                    if let Some(value) = value.as_str() {
-                                        if value.starts_with("*") {
-                                            let value = json!(value[1..]);
-                                            let field = data_view.properties.get(field_name).context("process 1 : context")?.as_item().context("as_ref")?;
-                                            filter[field_name] = server_connection.login_response.openapi.copy_value_field(field, true, &value).unwrap();
-                                        }
-                                    }
-                                }
-                            }
-                        }
-
-                        if filter.as_object().context("filter is not object")?.is_empty() == false {
-                            let list = vec![];
-                            let list_str = vec![];
-
-                            for _candidate in &service.list {
-                                // if Filter::match(filter, candidate) {
-                                //     list.push(candidate);
-                                //     let str = rufs_service.list_str[i];
-                                //     list_str.push(str);
-                                // }
-                            }
-
-                            (list, list_str)
-                        } else {
-                            (service.list.clone(), service.list_str.clone())
-                        }
-                    } else {
-                        println!("[build_field_filter_results] don't have acess to service {}", reference);
-                        (vec![], vec![])
-                    }
-                } else if let Some(enumeration) = extensions.get("x-enum") {
-                    let enumeration = enumeration.as_array().context("x-enum is not array")?;
-
-                    let list_str = if let Some(enum_labels) = extensions.get("x-enumLabels") {
-                        enum_labels.as_array().unwrap().iter().map(|s| s.as_str().unwrap().to_string()).collect()
-                    } else {
-                        enumeration.iter().map(|s| s.to_string()).collect()
-                    };
-
-                    (enumeration.clone(), list_str)
-                } else {
-                    (vec![], vec![])
-                };
+                    let date_ini = if date_end.day() > 15 { date_end.with_day(15).unwrap() } else { date_end.with_day(1).unwrap() };
 
-                data_view.field_results.insert(field_name.clone(), list.clone());
-                data_view.field_results_str.insert(field_name.clone(), list_str.clone());
-            }
+                    (date_ini, date_end)
+                }
+                "month_current" => (month_active_start, month_active_start.checked_add_months(Months::new(1)).unwrap()),
+                "month_previous" => (month_last_start, month_active_start),
+                "year_current" => (year_active_start, year_active_start.checked_add_months(Months::new(12)).unwrap()),
+                "year_previous" => (year_last_start, year_active_start),
+                _ => (date_ini, date_end),
+            };
 
-            Ok(())
+            data_view.instance_filter_range_min[field_name] = json!(date_ini.to_rfc3339());
+            data_view.instance_filter_range_max[field_name] = json!(date_end.to_rfc3339());
         }
 
         async fn data_view_get(watcher: &Box<dyn DataViewWatch>, data_view: &mut DataView, server_connection: &mut ServerConnection, primary_key: &Value, element_id: &HtmlElementId) -> Result<(), Box<dyn std::error::Error>> {
@@ -3106,12 +4954,20 @@ impl DataViewManager<'_> {
                 }
 
                 data_view_item.set_values(server_connection, watcher, &foreign_key, element_id)?;
+                // rows of the inline grid
+                let service = server_connection.service_map.get(&item.schema).context(format!("[data_view_get] Missing service {} in server_connection.service_map.", item.schema))?;
+                data_view_item.filter_results = service.find(&foreign_key).into_iter().cloned().collect();
+                data_view_item.removed_rows.clear();
+                data_view_item.active_index = None;
+                data_view_item.sort_rows();
             }
 
             data_view.active_primary_key = Some(primary_key);
             data_view.set_values(server_connection, watcher, &value, element_id)
         }
 
+        self.navigation_save();
+
         let is_first = if self.data_view_map.contains_key(&element_id.data_view_id.form_id_parent) == false {
             let path = if let Some(parent) = &element_id.data_view_id.parent_name {
                 format!("/{}", parent.to_case(convert_case::Case::Snake))
@@ -3133,7 +4989,7 @@ impl DataViewManager<'_> {
                             let path = format!("/{}", item.schema.to_case(convert_case::Case::Snake));
                             let mut data_view_item = DataView::new(&path, DataViewType::Dependent, Some(&data_view.data_view_id.schema_name.clone()), DataViewProcessAction::New);
                             data_view_item.set_schema(&self.server_connection)?;
-                            build_field_filter_results(&mut data_view_item, &self.server_connection)?;
+                            data_view_item.build_field_filter_results(&self.server_connection)?;
                             data_view.childs.push(data_view_item);
                         }
                     }
@@ -3146,6 +5002,7 @@ impl DataViewManager<'_> {
                     }
 
                     let field = field.as_item().context("data_view_get 1 : context")?;
+                    let schema_data = &field.schema_data;
 
                     match &field.schema_kind {
                         SchemaKind::Type(typ) => match &typ {
@@ -3158,7 +5015,8 @@ impl DataViewManager<'_> {
                                         Type::Object(schema) => {
                                             let mut data_view_item = DataView::new(field_name, DataViewType::ObjectProperty, Some(&data_view.data_view_id.schema_name.clone()), DataViewProcessAction::New);
                                             data_view_item.properties = schema.properties.clone();
-                                            build_field_filter_results(&mut data_view_item, &self.server_connection)?;
+                                            data_view_item.schema_data = schema_data.clone();
+                                            data_view_item.build_field_filter_results(&self.server_connection)?;
                                             data_view.childs.push(data_view_item);
                                         }
                                         _ => {}
@@ -3166,13 +5024,23 @@ impl DataViewManager<'_> {
                                     SchemaKind::Any(schema) => {
                                         let mut data_view_item = DataView::new(field_name, DataViewType::ObjectProperty, Some(&data_view.data_view_id.schema_name.clone()), DataViewProcessAction::New);
                                         data_view_item.properties = schema.properties.clone();
+                                        data_view_item.schema_data = schema_data.clone();
                                         data_view_item.short_description_list = data_view_item.properties.keys().map(|x| x.clone()).collect();
-                                        build_field_filter_results(&mut data_view_item, &self.server_connection)?;
+                                        data_view_item.build_field_filter_results(&self.server_connection)?;
                                         data_view.childs.push(data_view_item);
                                     }
-                                    _ => todo!(),
+                                    // scalar items are edited in the list of the parent form
+                                    _ => {}
                                 }
                             }
+                            // only one level of nesting fits in the element ids, objects inside it are not rendered
+                            Type::Object(schema) => {
+                                let mut data_view_item = DataView::new(field_name, DataViewType::ObjectNested, Some(&data_view.data_view_id.schema_name.clone()), action.clone());
+                                data_view_item.properties = schema.properties.clone();
+                                data_view_item.schema_data = schema_data.clone();
+                                data_view_item.build_field_filter_results(&self.server_connection)?;
+                                data_view.childs.push(data_view_item);
+                            }
                             _ => {}
                         },
                         _ => {}
@@ -3187,6 +5055,7 @@ impl DataViewManager<'_> {
         };
 
         let data_view = data_view_get_mut!(self, element_id);
+        data_view.check_access(&self.server_connection, DataView::method_from_action(action))?;
         data_view.clear();
         data_view.clear_filter()?;
         data_view.clear_sort()?;
@@ -3197,6 +5066,13 @@ impl DataViewManager<'_> {
             data_view.clear_filter()?;
             data_view.clear_sort()?;
             data_view.clear_aggregate();
+
+            // the rows of the inline grid are loaded again with the parent
+            if data_view.typ == DataViewType::Dependent {
+                data_view.filter_results.clear();
+                data_view.removed_rows.clear();
+                data_view.active_index = None;
+            }
         }
 
         if &data_view.action != action {
@@ -3204,7 +5080,8 @@ impl DataViewManager<'_> {
             data_view.set_schema(&self.server_connection)?;
 
             for data_view in &mut data_view.childs {
-                data_view.action = DataViewProcessAction::New;
+                // nested objects are part of the parent instance, so they follow its action
+                data_view.action = if data_view.typ == DataViewType::ObjectNested { action.clone() } else { DataViewProcessAction::New };
                 data_view.set_schema(&self.server_connection)?;
             }
         }
@@ -3215,8 +5092,12 @@ impl DataViewManager<'_> {
             // }
         }
 
+        // later, only the cascading options follow the instance, that was just cleared
         if is_first {
-            build_field_filter_results(data_view, &self.server_connection)?;
+            data_view.build_field_filter_results(&self.server_connection)?;
+        } else {
+            let cascading = data_view.cascading_fields();
+            data_view.build_field_filter_results_of(&self.server_connection, cascading)?;
         }
 
         match &data_view.action {
@@ -3303,12 +5184,186 @@ impl DataViewManager<'_> {
             data_view_response.tables[&data_view.data_view_id.form_id] = json!(table);
         }
 
+        data_view_response.lists = DataView::build_lists(self, data_view)?;
+        data_view_response.options = DataView::build_options(self, data_view)?;
+
         let data_view_parent = data_view_get_parent_mut!(self, element_id);
         data_view_parent.build_changes(element_id, &mut data_view_response.changes)?;
+        data_view_parent.build_states(&mut data_view_response.states);
+        self.navigation_push(&element_id.data_view_id.form_id_parent)?;
+        data_view_response.navigation = self.build_navigation();
         Ok(data_view_response)
     }
 
     async fn process_click_target(&mut self, target: &str) -> Result<DataViewResponse, Box<dyn std::error::Error>> {
+        log::debug!(target: LOG_EVENT, "process_click_target({})", target);
+        let re = regex::Regex::new(r"^navigation-(?P<index>back|forward|\d+)$")?;
+
+        if let Some(cap) = re.captures(target) {
+            let index = match cap.name("index").context("Missing index")?.as_str() {
+                "back" => self.navigation_index.checked_sub(1).context("Missing previous navigation entry")?,
+                "forward" => self.navigation_index + 1,
+                index => index.parse::<usize>()?,
+            };
+
+            return self.navigation_go(index);
+        }
+
+        // cancel leaves the form, and the field that was waiting for it
+        let re = regex::Regex::new(r"^cancel-(?P<form_type>instance|filter|aggregate|sort)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)$")?;
+
+        if let Some(cap) = re.captures(target) {
+            let element_id = HtmlElementId::new_with_regex(&cap)?;
+            self.reference_origins.remove(&element_id.data_view_id.form_id);
+
+            return match self.navigation_index.checked_sub(1) {
+                Some(index) => self.navigation_go(index),
+                None => Ok(DataViewResponse {
+                    form_id: element_id.data_view_id.form_id.clone(),
+                    ..Default::default()
+                }),
+            };
+        }
+
+        let re = regex::Regex::new(r"(?P<operation>list_add|list_remove|list_up|list_down)-(?P<form_type>instance)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)-(?P<field_name>[a-zA-Z][\w_]*)(-(?P<index>\d+))?$")?;
+
+        if let Some(cap) = re.captures(target) {
+            let element_id = &HtmlElementId::new_with_regex(&cap)?;
+            let field_name = element_id.field_name.clone().context("Missing field_name")?;
+            let data_view = data_view_get!(self, element_id);
+            let field = data_view.properties.get(&field_name).and_then(|field| field.as_item()).context(format!("Missing field {}", field_name))?;
+            let (array, item_schema) = DataView::list_item_schema(field).context(format!("Field {} is not a list", field_name))?;
+            let mut list = data_view.instance.get(&field_name).and_then(|value| value.as_array()).cloned().unwrap_or_default();
+            let index = element_id.index.unwrap_or(list.len());
+            // the list is saved with the parent, so it follows the action and the access of the parent form
+            let data_view_parent = data_view_get_parent!(self, element_id);
+
+            if !matches!(data_view_parent.action, DataViewProcessAction::New | DataViewProcessAction::Edit) {
+                Err(format!("{} can't be changed in {} mode", field_name, data_view_parent.action))?;
+            }
+
+            data_view_parent.check_access(&self.server_connection, DataView::method_from_action(&data_view_parent.action))?;
+
+            match cap.name("operation").map(|operation| operation.as_str()) {
+                Some("list_add") => {
+                    if array.max_items.is_some_and(|max_items| list.len() >= max_items) {
+                        Err(format!("{} accepts at most {} items", field_name, array.max_items.unwrap_or_default()))?;
+                    }
+
+                    list.push(DataView::list_item_default(item_schema));
+                }
+                Some("list_remove") => {
+                    if array.min_items.is_some_and(|min_items| list.len() <= min_items) {
+                        Err(format!("{} requires at least {} items", field_name, array.min_items.unwrap_or_default()))?;
+                    }
+
+                    if index < list.len() {
+                        list.remove(index);
+                    }
+                }
+                Some("list_up") if index > 0 && index < list.len() => list.swap(index, index - 1),
+                Some("list_down") if index + 1 < list.len() => list.swap(index, index + 1),
+                _ => {}
+            }
+
+            let mut data_view_response = DataViewResponse { changes: json!({}), ..Default::default() };
+            let data_view_parent = data_view_get_parent_mut!(self, element_id);
+            data_view_parent.set_value(&self.server_connection, self.watcher.as_ref(), &field_name, &json!(list), element_id)?;
+            data_view_parent.build_changes(element_id, &mut data_view_response.changes)?;
+            data_view_parent.build_states(&mut data_view_response.states);
+            let data_view = data_view_get!(self, element_id);
+            data_view_response.lists = DataView::build_lists(self, data_view)?;
+            return Ok(data_view_response);
+        }
+
+        let re = regex::Regex::new(r"reference-(?P<operation>view|create|search)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)-(?P<field_name>[a-zA-Z][\w_]*)$")?;
+
+        if let Some(cap) = re.captures(target) {
+            let origin = HtmlElementId::new_with_regex(&cap)?;
+            let field_name = origin.field_name.clone().context("Missing field_name")?;
+            let data_view = data_view_get!(self, origin);
+            let field = data_view.properties.get(&field_name).and_then(|field| field.as_item()).context(format!("Missing field {}", field_name))?;
+            let reference = field.schema_data.extensions.get("x-$ref").and_then(|reference| reference.as_str()).context(format!("Field {} is not a reference", field_name))?;
+            let schema_name = OpenAPI::get_schema_name_from_ref(reference);
+            let element_id = HtmlElementId::new(schema_name, None, FormType::Instance, None, None, None);
+            let mut params_search = DataViewProcessParams { ..Default::default() };
+
+            let action = match cap.name("operation").map(|operation| operation.as_str()) {
+                Some("view") => {
+                    let item = self
+                        .server_connection
+                        .login_response
+                        .openapi
+                        .get_primary_key_foreign(&data_view.data_view_id.schema_name, &field_name, &data_view.instance)?
+                        .filter(|item| item.valid)
+                        .context(format!("Field {} is empty", field_name))?;
+                    params_search.primary_key = Some(item.primary_key);
+                    DataViewProcessAction::View
+                }
+                Some("create") => DataViewProcessAction::New,
+                _ => {
+                    // the list starts narrowed as the options of the field
+                    let mut filter = json!({});
+
+                    for (foreign_name, name) in DataView::reference_cascade(field) {
+                        if let Some(value) = data_view.instance.get(&name).filter(|value| !value.is_null()) {
+                            filter[foreign_name] = value.clone();
+                        }
+                    }
+
+                    if filter.as_object().is_some_and(|filter| !filter.is_empty()) {
+                        params_search.filter = Some(filter);
+                    }
+
+                    params_search.select_out = Some(field_name.clone());
+                    DataViewProcessAction::Search
+                }
+            };
+
+            if action != DataViewProcessAction::View {
+                let data_view_parent = data_view_get_parent!(self, origin);
+
+                let reference_origin = ReferenceOrigin {
+                    action: data_view_parent.action.clone(),
+                    instance: data_view_parent.instance.clone(),
+                    select: action == DataViewProcessAction::Search,
+                    element_id: origin,
+                };
+
+                self.reference_origins.insert(element_id.data_view_id.form_id.clone(), reference_origin);
+            }
+
+            return self.process_data_view_action(&element_id, &action, &params_search, &json!({})).await;
+        }
+
+        let re = regex::Regex::new(r"search_select-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)-(?P<field_name>[a-zA-Z][\w_]*)-(?P<index>\d+)$")?;
+
+        if let Some(cap) = re.captures(target) {
+            let element_id = HtmlElementId::new_with_regex(&cap)?;
+            let data_view = data_view_get!(self, element_id);
+            let form_id = data_view.data_view_id.form_id.clone();
+            // item_index counts the rows of the page
+            let index = (data_view.current_page - 1) * data_view.page_size + element_id.index.context("broken index")?;
+
+            let list = if data_view.uses_filter_results() {
+                &data_view.filter_results
+            } else {
+                let service = self.server_connection.service_map.get(&data_view.data_view_id.schema_name).context("broken service")?;
+                &service.list
+            };
+
+            let obj = list.get(index).context(format!("Missing {}.filter_results[{}], size = {}", form_id, index, list.len()))?.clone();
+            // the origin of a pending reference-create is kept for the save of the new record
+            if !self.reference_origins.get(&form_id).is_some_and(|origin| origin.select) {
+                Err(format!("No field waiting for a selection of {}", form_id))?;
+            }
+
+            let origin = self.reference_origins.remove(&form_id).context(format!("No field waiting for a selection of {}", form_id))?;
+            let mut data_view_response = DataViewResponse { form_id, changes: json!({}), ..Default::default() };
+            self.return_reference(&origin, &obj, &mut data_view_response)?;
+            return Ok(data_view_response);
+        }
+
         let re = regex::Regex::new(r"(?P<action>create)-(?P<form_type>instance|filter|aggregate|sort)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)$")?;
 
         if let Some(cap) = re.captures(target) {
@@ -3318,16 +5373,41 @@ impl DataViewManager<'_> {
             return self.process_data_view_action(&element_id, &crate::DataViewProcessAction::New, &params_search, &params_extra).await;
         }
 
-        let re = regex::Regex::new(r"delete-(?P<form_type>instance|filter|aggregate|sort)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)")?;
+        let re = regex::Regex::new(r"(?P<operation>delete|delete_confirm)-(?P<form_type>instance|filter|aggregate|sort)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)")?;
 
         if let Some(cap) = re.captures(target) {
             let element_id = HtmlElementId::new_with_regex(&cap)?;
             let data_view = data_view_get!(self, element_id);
+            data_view.check_access(&self.server_connection, "delete")?;
+            let schema_name = data_view.data_view_id.schema_name.clone();
             let primary_key = data_view
                 .active_primary_key
-                .as_ref()
+                .clone()
                 .context(format!("don't opened item in form_id {}", data_view.data_view_id.form_id))?;
-            let _old_value = self.server_connection.remove(&data_view.data_view_id.schema_name, primary_key).await?;
+
+            // the first click shows what is going to be removed, the record is removed by delete_confirm
+            if cap.name("operation").is_some_and(|operation| operation.as_str() == "delete") {
+                let mut counts = vec![];
+                let mut removals = vec![];
+                self.delete_dependents(&schema_name, &primary_key, &mut counts, &mut removals)?;
+
+                return Ok(DataViewResponse {
+                    form_id: element_id.data_view_id.form_id.clone(),
+                    confirm: json!({"target": target.replacen("delete-", "delete_confirm-", 1), "message": self.delete_confirm_message(&counts)}),
+                    ..Default::default()
+                });
+            }
+
+            let save_results = self.remove_with_dependents(&element_id.data_view_id.form_id, &schema_name, &primary_key).await?;
+
+            if save_results.iter().any(|save_result| save_result.error.is_some()) {
+                return Ok(DataViewResponse {
+                    form_id: element_id.data_view_id.form_id.clone(),
+                    save_results,
+                    ..Default::default()
+                });
+            }
+
             let params_search = DataViewProcessParams { ..Default::default() };
             let params_extra = json!({});
             return self.process_data_view_action(&element_id, &crate::DataViewProcessAction::Search, &params_search, &params_extra).await;
@@ -3347,97 +5427,405 @@ impl DataViewManager<'_> {
                     };
 
                     let data_view = data_view_get_parent_mut!(self, element_id);
+                    data_view.check_access(&self.server_connection, DataView::method_from_action(&data_view.action))?;
+                    data_view.check_field_states()?;
+
+                    if let Some(child_name) = child_name {
+                        let child = data_view.childs.iter().find(|item| item.data_view_id.schema_name == child_name).context(format!("Missing child {}", child_name))?;
+                        child.check_field_states()?;
+                    }
+
+                    let row_errors = data_view.check_grid_rows();
+
+                    if !row_errors.is_empty() {
+                        return Ok(DataViewResponse {
+                            form_id: element_id.data_view_id.form_id.clone(),
+                            save_results: row_errors,
+                            ..Default::default()
+                        });
+                    }
+
                     let (is_ok, action) = self.watcher.check_save(data_view, child_name, &self.server_connection, &element_id)?;
 
+                    let action = if action == DataViewProcessAction::Edit && !data_view.is_allowed(&self.server_connection, "put") {
+                        DataViewProcessAction::View
+                    } else {
+                        action
+                    };
+
                     if is_ok {
-                        let obj_in = data_view.save(&mut self.server_connection).await?;
+                        let (params_extra, save_results) = data_view.save_with_dependents(&mut self.server_connection, child_name).await?;
+
+                        if save_results.iter().any(|save_result| save_result.error.is_some()) {
+                            DataViewResponse {
+                                form_id: element_id.data_view_id.form_id.clone(),
+                                save_results,
+                                ..Default::default()
+                            }
+                        } else {
+                            let params_search = DataViewProcessParams { ..Default::default() };
+                            let mut data_view_response = self.process_data_view_action(&element_id, &action, &params_search, &params_extra).await?;
+                            data_view_response.save_results = save_results;
+
+                            // the record created by reference-create goes back to the field that asked for it
+                            if child_name.is_none() {
+                                if let Some(origin) = self.reference_origins.remove(&element_id.data_view_id.form_id) {
+                                    self.return_reference(&origin, &params_extra, &mut data_view_response)?;
+                                }
+                            }
+
+                            data_view_response
+                        }
+                    } else {
+                        DataViewResponse { ..Default::default() }
+                    }
+                }
+                FormType::Filter => {
+                    let mut params_search = DataViewProcessParams::default();
+                    let data_view = data_view_get!(self, element_id);
+                    params_search.filter = Some(data_view.instance_filter.clone());
+                    params_search.filter_range = Some(data_view.instance_filter_range.clone());
+                    params_search.filter_range_min = Some(data_view.instance_filter_range_min.clone());
+                    params_search.filter_range_max = Some(data_view.instance_filter_range_max.clone());
+                    self.process_data_view_action(&element_id, &DataViewProcessAction::Search, &params_search, &json!({})).await?
+                }
+                FormType::Aggregate => {
+                    let data_view = data_view_get_mut!(self, element_id);
+                    let aggregate = data_view.instance_aggregate_range.clone();
+                    data_view.apply_aggregate(&self.server_connection, &aggregate)?;
+                    let mut data_view_response = DataViewResponse { ..Default::default() };
+                    data_view_response.aggregates[&data_view.data_view_id.form_id] = json!(data_view.aggregate_results);
+                    data_view_response
+                }
+                FormType::Sort => todo!(),
+            };
+
+            return Ok(data_view_response);
+        }
+
+        let re = regex::Regex::new(r"table-row-(?P<action>new|edit|view|search)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)(-(?P<field_name>[\w_]+))?-(?P<index>\d+)")?;
+
+        if let Some(cap) = re.captures(target) {
+            let element_id = HtmlElementId::new_with_regex(&cap)?;
+            let data_view = data_view_get_mut!(self, element_id);
+
+            let data_view = if let Some(field_name) = element_id.field_name.as_ref() {
+                data_view
+                .childs
+                .iter_mut()
+                .find(|data_view| &data_view.data_view_id.schema_name == field_name)
+                .context(format!("Missing child {} in {}", field_name, data_view.data_view_id.form_id))?
+            } else {
+                data_view
+            };
+
+            let schema_name = &data_view.data_view_id.schema_name;
+            let active_index = element_id.index.context("broken index")?;
+
+            let list = if data_view.uses_filter_results() {
+                &data_view.filter_results
+            } else {
+                let service = self.server_connection.service_map.get(schema_name).context("broken service")?;
+                &service.list
+            };
+    
+            let instance = list.get(active_index).context(format!("Missing {}.filter_results[{}], size = {}", schema_name, active_index, list.len()))?.clone();
+            data_view.active_index = Some(active_index);
+            let params_search = DataViewProcessParams { ..Default::default() };
+            let action = element_id.action.context("Missing action")?;
+            return self.process_data_view_action(&element_id, &action, &params_search, &instance).await;
+        }
+
+        let re = regex::Regex::new(r"^trash-(?P<name>[\w_]+)$")?;
+
+        if let Some(cap) = re.captures(target) {
+            let element_id = HtmlElementId::new_with_regex(&cap)?;
+            let data_view = data_view_get_mut!(self, element_id);
+            data_view.trash = !data_view.trash;
+            data_view.current_page = 1;
+            let data_view = data_view_get!(self, element_id);
+            let params_search = DataViewProcessParams { ..Default::default() };
+            let mut data_view_response = DataViewResponse { form_id: data_view.data_view_id.form_id.clone(), tables: json!({}), ..Default::default() };
+            let table = DataView::build_table(self, data_view, &params_search)?;
+            data_view_response.tables[&data_view.data_view_id.form_id] = json!(table);
+            return Ok(data_view_response);
+        }
+
+        let re = regex::Regex::new(r"^table-row-restore-(?P<name>[\w_]+)-(?P<index>\d+)$")?;
+
+        if let Some(cap) = re.captures(target) {
+            let element_id = HtmlElementId::new_with_regex(&cap)?;
+            let data_view = data_view_get!(self, element_id);
+            // restoring is undoing a delete, so it takes the same permission
+            data_view.check_access(&self.server_connection, "delete")?;
+            let schema_name = &data_view.data_view_id.schema_name;
+            let path = data_view.path.clone().context(format!("Missing path of {}", data_view.data_view_id.form_id))?;
+            let service = self.server_connection.service_map.get(schema_name).context(format!("Missing service {} in service_map", schema_name))?;
+            let column = service.soft_delete.clone().context(format!("Missing x-softDelete in {}", schema_name))?;
+            let index = element_id.index.context("Missing index")?;
+            let mut obj = service.list_deleted.get(index).cloned().context(format!("Missing {}[{}] in the trash", schema_name, index))?;
+            obj[&column] = Value::Null;
+            let obj_in = self.server_connection.send_update(&path, &obj).await?;
+            let primary_key = self.server_connection.service_map.get(&data_view.data_view_id.schema_name).and_then(|service| service.get_primary_key(&obj_in));
+            let data_view = data_view_get!(self, element_id);
+            let params_search = DataViewProcessParams { ..Default::default() };
+            let mut data_view_response = DataViewResponse { form_id: data_view.data_view_id.form_id.clone(), tables: json!({}), ..Default::default() };
+            data_view_response.save_results.push(DataViewSaveResult::new(&data_view.data_view_id.form_id, Some(index), primary_key, None));
+            let table = DataView::build_table(self, data_view, &params_search)?;
+            data_view_response.tables[&data_view.data_view_id.form_id] = json!(table);
+            return Ok(data_view_response);
+        }
+
+        let re = regex::Regex::new(r"^(clone-instance|table-row-clone)-(?P<name>[\w_]+)(-(?P<index>\d+))?$")?;
+
+        if let Some(cap) = re.captures(target) {
+            let element_id = HtmlElementId::new_with_regex(&cap)?;
+            let data_view = data_view_get!(self, element_id);
+            data_view.check_access(&self.server_connection, "post")?;
+            let schema_name = &data_view.data_view_id.schema_name;
+            let openapi = &self.server_connection.login_response.openapi;
+            let service = self.server_connection.service_map.get(schema_name).context(format!("Missing service {} in service_map", schema_name))?;
+
+            let source = if let Some(index) = element_id.index {
+                let list = if data_view.uses_filter_results() { &data_view.filter_results } else { &service.list };
+                list.get(index).context(format!("Missing {}[{}], size = {}", schema_name, index, list.len()))?.clone()
+            } else {
+                let primary_key = data_view.active_primary_key.as_ref().context(format!("don't opened item in form_id {}", data_view.data_view_id.form_id))?;
+                service.find_one(primary_key).cloned().context(format!("Missing {} in {}", primary_key, schema_name))?
+            };
+
+            let values = data_view.clone_values(&self.server_connection, &source, true);
+            let source_key = service.get_primary_key(&source).context(format!("Missing primary key of {}", schema_name))?;
+
+            // x-cloneDependents lists the Dependent schemas whose rows are copied to the new record
+            let clone_dependents = data_view
+                .schema_data
+                .extensions
+                .get("x-cloneDependents")
+                .and_then(|value| value.as_array())
+                .map(|list| list.iter().filter_map(|item| item.as_str()).map(|item| item.to_string()).collect::<Vec<String>>())
+                .unwrap_or_default();
+
+            let mut rows: Vec<(String, Vec<Value>)> = vec![];
+
+            for item in openapi.get_dependents(schema_name, false) {
+                if !clone_dependents.contains(&item.schema) || rows.iter().any(|(schema, _)| schema == &item.schema) {
+                    continue;
+                }
+
+                let Some(child) = data_view.childs.iter().find(|child| child.typ == DataViewType::Dependent && child.data_view_id.schema_name == item.schema) else {
+                    continue;
+                };
+
+                let Some(foreign_key) = openapi.get_foreign_key(&item.schema, &item.field, &source_key)? else {
+                    continue;
+                };
+
+                let child_service = self.server_connection.service_map.get(&item.schema).context(format!("Missing service {} in service_map", item.schema))?;
+                let mut child_rows = vec![];
+
+                for row in child_service.find(&foreign_key) {
+                    let mut row = child.clone_values(&self.server_connection, row, false);
+
+                    // the key of the new parent is set when it is saved, and the own key of the row isn't copied,
+                    // otherwise the save would find the source row stored and move it to the clone
+                    if let (Some(row), Some(foreign_key)) = (row.as_object_mut(), foreign_key.as_object()) {
+                        row.retain(|field_name, _| {
+                            let is_reference = child
+                                .properties
+                                .get(field_name)
+                                .and_then(|field| field.as_item())
+                                .is_some_and(|field| field.schema_data.extensions.contains_key("x-$ref"));
+
+                            !foreign_key.contains_key(field_name) && (is_reference || !child_service.primary_keys.contains(field_name))
+                        });
+                    }
+
+                    child_rows.push(row);
+                }
+
+                rows.push((item.schema.clone(), child_rows));
+            }
+
+            let params_search = DataViewProcessParams { ..Default::default() };
+            let mut data_view_response = self.process_data_view_action(&element_id, &DataViewProcessAction::New, &params_search, &values).await?;
+
+            if rows.is_empty() {
+                return Ok(data_view_response);
+            }
+
+            let data_view = data_view_get_mut!(self, element_id);
+
+            for (schema, child_rows) in rows {
+                if let Some(child) = data_view.childs.iter_mut().find(|child| child.typ == DataViewType::Dependent && child.data_view_id.schema_name == schema) {
+                    child.filter_results = child_rows;
+                    child.sort_rows();
+                }
+            }
+
+            let data_view = data_view_get!(self, element_id);
+
+            for data_view in &data_view.childs {
+                let table = DataView::build_table(self, data_view, &params_search)?;
+                data_view_response.tables[&data_view.data_view_id.form_id] = json!(table);
+            }
+
+            return Ok(data_view_response);
+        }
+
+        let re = regex::Regex::new(r"^bulk_(?P<operation>select|select_all|select_none|remove|remove_confirm|update|export|continue)-(?P<name>[\w_]+)(-(?P<index>\d+))?$")?;
+
+        if let Some(cap) = re.captures(target) {
+            let element_id = &HtmlElementId::new_with_regex(&cap)?;
+            let form_id = element_id.data_view_id.form_id.clone();
+            let operation = cap.name("operation").context("Missing operation")?.as_str();
+            let data_view = data_view_get!(self, element_id);
+            let schema_name = &data_view.data_view_id.schema_name;
+            let service = self.server_connection.service_map.get(schema_name).context(format!("Missing service {} in service_map", schema_name))?;
+            // the rows shown in the table
+            let list = if data_view.trash {
+                &service.list_deleted
+            } else if data_view.uses_filter_results() {
+                &data_view.filter_results
+            } else {
+                &service.list
+            };
+
+            let mut data_view_response = DataViewResponse { form_id: form_id.clone(), ..Default::default() };
+
+            match operation {
+                "select" => {
+                    let index = element_id.index.context("Missing index")?;
+                    let primary_key = list.get(index).and_then(|item| service.get_primary_key(item)).context(format!("Missing row {} in {}", index, form_id))?;
+                    let data_view = data_view_get_mut!(self, element_id);
 
-                        if data_view.typ == DataViewType::ObjectProperty {
-                            if let Some(index) = data_view.active_index {
-                                data_view.filter_results[index] = obj_in.clone();
-                            }
+                    match data_view.selected_rows.iter().position(|item| item == &primary_key) {
+                        Some(pos) => {
+                            data_view.selected_rows.remove(pos);
                         }
+                        None => data_view.selected_rows.push(primary_key),
+                    }
+                }
+                "select_all" => {
+                    let primary_keys = list.iter().filter_map(|item| service.get_primary_key(item)).collect();
+                    let data_view = data_view_get_mut!(self, element_id);
+                    data_view.selected_rows = primary_keys;
+                }
+                "select_none" => {
+                    let data_view = data_view_get_mut!(self, element_id);
+                    data_view.selected_rows.clear();
+                }
+                "export" => {
+                    if data_view.selected_rows.is_empty() {
+                        Err(format!("No rows selected in {}", form_id))?;
+                    }
 
-                        let params_extra = if element_id.data_view_id.parent_name.is_some() {
-                            let data_view = data_view_get_mut!(self, element_id);
+                    let content = data_view.export_selected(&self.server_connection, &self.locale)?;
+                    data_view_response.download = json!({"name": format!("{}.csv", form_id), "media_type": "text/csv", "content": content});
+                    return Ok(data_view_response);
+                }
+                "remove" => {
+                    if data_view.selected_rows.is_empty() {
+                        Err(format!("No rows selected in {}", form_id))?;
+                    }
 
-                            if data_view.path.is_some() {
-                                let obj_in = data_view.save(&mut self.server_connection).await?;
+                    data_view.check_access(&self.server_connection, "delete")?;
+                    // the rows and their dependents are shown before they are removed by bulk_remove_confirm
+                    let mut counts = vec![];
+                    let mut removals = vec![];
 
-                                if data_view.typ == DataViewType::ObjectProperty {
-                                    if let Some(index) = data_view.active_index {
-                                        data_view.filter_results[index] = obj_in.clone();
-                                    }
-                                }
+                    for primary_key in &data_view.selected_rows {
+                        self.delete_dependents(schema_name, primary_key, &mut counts, &mut removals)?;
+                    }
 
-                                obj_in
-                            } else {
-                                json!({})
-                            }
-                        } else {
-                            obj_in                            
-                        };
+                    let message = format!("{}\n{} : {}", self.delete_confirm_message(&counts), self.locale.translate("selected"), data_view.selected_rows.len());
+                    data_view_response.confirm = json!({"target": target.replacen("bulk_remove-", "bulk_remove_confirm-", 1), "message": message});
+                    return Ok(data_view_response);
+                }
+                "remove_confirm" | "update" => {
+                    if data_view.selected_rows.is_empty() {
+                        Err(format!("No rows selected in {}", form_id))?;
+                    }
 
-                        let params_search = DataViewProcessParams { ..Default::default() };
-                        self.process_data_view_action(&element_id, &action, &params_search, &params_extra).await?
+                    let field_name = if operation == "update" {
+                        data_view.check_access(&self.server_connection, "put")?;
+                        let field_name = data_view.bulk_field.clone().context("Missing field of the bulk update")?;
+
+                        if !data_view.bulk_fields(&self.server_connection, &self.locale).iter().any(|(name, _)| name == &field_name) {
+                            Err(format!("Field {} can't be set in the selected rows of {}", field_name, form_id))?;
+                        }
+
+                        Some(field_name)
                     } else {
-                        DataViewResponse { ..Default::default() }
-                    }
-                }
-                FormType::Filter => {
-                    let mut params_search = DataViewProcessParams::default();
-                    let data_view = data_view_get!(self, element_id);
-                    params_search.filter = Some(data_view.instance_filter.clone());
-                    params_search.filter_range = Some(data_view.instance_filter_range.clone());
-                    params_search.filter_range_min = Some(data_view.instance_filter_range_min.clone());
-                    params_search.filter_range_max = Some(data_view.instance_filter_range_max.clone());
-                    self.process_data_view_action(&element_id, &DataViewProcessAction::Search, &params_search, &json!({})).await?
-                }
-                FormType::Aggregate => {
-                    let data_view = data_view_get_mut!(self, element_id);
-                    let aggregate = data_view.instance_aggregate_range.clone();
-                    data_view.apply_aggregate(&self.server_connection, &aggregate)?;
-                    let mut data_view_response = DataViewResponse { ..Default::default() };
-                    data_view_response.aggregates[&data_view.data_view_id.form_id] = json!(data_view.aggregate_results);
-                    data_view_response
+                        data_view.check_access(&self.server_connection, "delete")?;
+                        None
+                    };
+
+                    let bulk = BulkOperation {
+                        operation: operation.trim_end_matches("_confirm").to_string(),
+                        field_name,
+                        value: data_view.bulk_value.clone(),
+                        primary_keys: data_view.selected_rows.clone(),
+                        results: vec![],
+                    };
+
+                    self.bulk_operations.insert(form_id, bulk);
+                    return self.bulk_step(element_id).await;
                 }
-                FormType::Sort => todo!(),
-            };
+                _ => return self.bulk_step(element_id).await,
+            }
 
+            let data_view = data_view_get!(self, element_id);
+            let params_search = DataViewProcessParams { ..Default::default() };
+            data_view_response.tables = json!({});
+            let table = DataView::build_table(self, data_view, &params_search)?;
+            data_view_response.tables[&data_view.data_view_id.form_id] = json!(table);
             return Ok(data_view_response);
         }
 
-        let re = regex::Regex::new(r"table-row-(?P<action>new|edit|view|search)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)(-(?P<field_name>[\w_]+))?-(?P<index>\d+)")?;
+        let re = regex::Regex::new(r"^table-row-(?P<operation>add|remove|up|down)-(?P<parent>[\w_]+)-(?P<name>[\w_]+)(-(?P<index>\d+))?$")?;
 
         if let Some(cap) = re.captures(target) {
-            let element_id = HtmlElementId::new_with_regex(&cap)?;
+            let element_id = &HtmlElementId::new_with_regex(&cap)?;
+            let child_name = element_id.data_view_id.schema_name.clone();
             let data_view = data_view_get_mut!(self, element_id);
 
-            let data_view = if let Some(field_name) = element_id.field_name.as_ref() {
-                data_view
-                .childs
-                .iter_mut()
-                .find(|data_view| &data_view.data_view_id.schema_name == field_name)
-                .context(format!("Missing child {} in {}", field_name, data_view.data_view_id.form_id))?
-            } else {
-                data_view
-            };
+            if !data_view.is_grid() {
+                Err(format!("Rows of {} aren't edited inline", data_view.data_view_id.form_id))?;
+            }
 
-            let schema_name = &data_view.data_view_id.schema_name;
-            let active_index = element_id.index.context("broken index")?;
+            let len = data_view.filter_results.len();
+            let index = element_id.index.unwrap_or(len);
 
-            let list = if data_view.path.is_none() || data_view.filter_results.len() > 0 {
-                &data_view.filter_results
-            } else {
-                let service = self.server_connection.service_map.get(schema_name).context("broken service")?;
-                &service.list
-            };
-    
-            let instance = list.get(active_index).context(format!("Missing {}.filter_results[{}], size = {}", schema_name, active_index, list.len()))?.clone();
-            data_view.active_index = Some(active_index);
-            let params_search = DataViewProcessParams { ..Default::default() };
-            let action = element_id.action.context("Missing action")?;
-            return self.process_data_view_action(&element_id, &action, &params_search, &instance).await;
+            match cap.name("operation").map(|operation| operation.as_str()) {
+                Some("add") => {
+                    let row = data_view.row_default();
+                    data_view.filter_results.push(row);
+                    // the new row is in the last page
+                    data_view.current_page = (len / data_view.page_size) + 1;
+                }
+                Some("remove") if index < len => {
+                    let row = data_view.filter_results.remove(index);
+
+                    // the stored rows are deleted when the parent is saved
+                    if data_view.typ == DataViewType::Dependent {
+                        let service = self.server_connection.service_map.get(&child_name).context(format!("Missing service {} in service_map", child_name))?;
+
+                        if let Some(primary_key) = service.get_primary_key(&row).filter(|primary_key| service.find_pos(primary_key).is_some()) {
+                            data_view.removed_rows.push(primary_key);
+                        }
+                    }
+                }
+                Some("up") if index > 0 && index < len => data_view.filter_results.swap(index, index - 1),
+                Some("down") if index + 1 < len => data_view.filter_results.swap(index, index + 1),
+                _ => {}
+            }
+
+            data_view.active_index = None;
+            data_view.renumber_rows();
+            let data_view_parent = data_view_get_parent_mut!(self, element_id);
+            data_view_parent.sync_grid(&self.server_connection, self.watcher.as_ref(), &child_name)?;
+            return self.build_grid_response(element_id);
         }
 
         let re = regex::Regex::new(r"(?P<act>sort_left|sort_toggle|sort_rigth)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)-(?P<field_name>[\w_]+)")?;
@@ -3456,7 +5844,7 @@ impl DataViewManager<'_> {
                 }
             }
 
-            if data_view.filter_results.is_empty() {
+            if !data_view.uses_filter_results() {
                 let service = self.server_connection.service_map.get(&data_view.data_view_id.schema_name).context("Missing service in service_map")?;
                 data_view.filter_results = service.list.clone();
             }
@@ -3531,12 +5919,42 @@ impl DataViewManager<'_> {
             };
 
             let action = &element_id.action.context("broken")?;
+            // opened from a link, the form isn't answering a reference field anymore
+            self.reference_origins.remove(&element_id.data_view_id.form_id);
             return self.process_data_view_action(&element_id, action, &params_search, &params_extra).await;
         }
 
         None.context("unknow click taget")?
     }
 
+    async fn process_typeahead_target(&mut self, target: &str, value: &str) -> Result<DataViewResponse, Box<dyn std::error::Error>> {
+        // the cells of the inline grid have the suggestions of the instance field
+        let re = regex::Regex::new(r"^((?P<form_type>instance|filter)|table-row-col)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)-(?P<field_name>[a-zA-Z][\w_]*)(-\d+)?$")?;
+        let cap = re.captures(target).context(format!("unknow typeahead target {}", target))?;
+        let element_id = &HtmlElementId::new_with_regex(&cap)?;
+        let field_name = element_id.field_name.clone().context("Missing field_name")?;
+        let data_view = data_view_get!(self, element_id);
+        let field = data_view.properties.get(&field_name).and_then(|field| field.as_item()).context(format!("Missing field {}", field_name))?;
+        let reference = field.schema_data.extensions.get("x-$ref").and_then(|reference| reference.as_str()).context(format!("Field {} is not a reference", field_name))?;
+        let schema_name = OpenAPI::get_schema_name_from_ref(reference);
+
+        // services that aren't kept in cache are searched in the server by the typed text, the rows found
+        // are the options of the field, so the chosen description is resolved to its key by process_edit_target
+        if self.server_connection.service_map.get(&schema_name).is_some_and(|service| !service.loaded) {
+            let (list, list_str) = self.server_connection.search_remote(&schema_name, value, TYPEAHEAD_LIMIT).await?;
+            let data_view = data_view_get_mut!(self, element_id);
+            data_view.field_results.insert(field_name.clone(), list);
+            data_view.field_results_str.insert(field_name.clone(), list_str);
+        }
+
+        let data_view = data_view_get!(self, element_id);
+        let empty_list = vec![];
+        let list_str = data_view.field_results_str.get(&field_name).unwrap_or(&empty_list);
+        let mut data_view_response = DataViewResponse { suggestions: json!({}), ..Default::default() };
+        data_view_response.suggestions[target] = json!(DataView::rank_options(list_str, value, TYPEAHEAD_LIMIT));
+        Ok(data_view_response)
+    }
+
     async fn process_edit_target(&mut self, target: &str, value: &str) -> Result<DataViewResponse, Box<dyn std::error::Error>> {
         fn parse_value_process(data_view: &DataView, server_connection: &ServerConnection, element_id: &HtmlElementId, value: &str) -> Result<(Value, bool), Box<dyn std::error::Error>> {
             //data_view.field_external_references_str.insert(field_name.to_string(), value.to_string());
@@ -3551,8 +5969,33 @@ impl DataViewManager<'_> {
             let field = field.as_item().context("[process_edit_target.parse_value({})] broken")?;
             let extensions = &field.schema_data.extensions;
             let mut is_flags = false;
+            let value = &mask::strip_value(extensions, value, &server_connection.locale);
+
+            let value = if let Some("byte") = DataView::file_encoding(field) {
+                let max_length = match &field.schema_kind {
+                    SchemaKind::Type(Type::String(typ)) => typ.max_length,
+                    _ => None,
+                };
+
+                if max_length.is_some_and(|max_length| value.len() > max_length) {
+                    Err(format!("File of {} exceeds the maximum size of {} encoded bytes", field_name, max_length.unwrap_or_default()))?;
+                }
+
+                json!(value)
+            } else if let Some((_, item_schema)) = DataView::list_item_schema(field) {
+                let index = element_id.index.context("Missing list index")?;
+                let field_value = data_view.get_form_type_instance(&element_id.form_type, &element_id.form_type_ext)?.get(field_name).unwrap_or(&Value::Null);
+                let mut list = field_value.as_array().cloned().unwrap_or_default();
+                let item = DataView::parse_list_item(server_connection, field_name, item_schema, value)?;
+
+                if index < list.len() {
+                    list[index] = item;
+                } else {
+                    list.push(item);
+                }
 
-            let value = if let Some(_) = extensions.get("x-flags") {
+                json!(list)
+            } else if let Some(_) = extensions.get("x-flags") {
                 let index = element_id.index.context("Missing flag_index")?;
                 let field_value = data_view.get_form_type_instance(&element_id.form_type, &element_id.form_type_ext)?.get(field_name).unwrap_or(&Value::Null);
                 let field_value = field_value.as_u64().context("Is not u64")?;
@@ -3615,8 +6058,74 @@ impl DataViewManager<'_> {
             Ok((value, is_flags))
         }
 
+        let re = regex::Regex::new(r"^bulk_(?P<input>field|value)-(?P<name>[\w_]+)$")?;
+
+        if let Some(cap) = re.captures(target) {
+            let element_id = &HtmlElementId::new_with_regex(&cap)?;
+            let data_view = data_view_get_mut!(self, element_id);
+            let mut data_view_response = DataViewResponse { ..Default::default() };
+
+            if cap.name("input").is_some_and(|input| input.as_str() == "field") {
+                if !value.is_empty() && !data_view.bulk_fields(&self.server_connection, &self.locale).iter().any(|(name, _)| name == value) {
+                    Err(format!("Field {} can't be set in the selected rows of {}", value, data_view.data_view_id.form_id))?;
+                }
+
+                data_view.bulk_field = Some(value.to_string()).filter(|field_name| !field_name.is_empty());
+                data_view.bulk_value = Value::Null;
+                // the value input takes the options of the field
+                let data_view = data_view_get!(self, element_id);
+                let params_search = DataViewProcessParams { ..Default::default() };
+                data_view_response.tables = json!({});
+                let table = DataView::build_table(self, data_view, &params_search)?;
+                data_view_response.tables[&data_view.data_view_id.form_id] = json!(table);
+            } else {
+                let mut field_id = element_id.clone();
+                field_id.field_name = Some(data_view.bulk_field.clone().context("Missing field of the bulk update")?);
+                let data_view = data_view_get!(self, element_id);
+                let (value, _) = parse_value_process(data_view, &self.server_connection, &field_id, value)?;
+                let data_view = data_view_get_mut!(self, element_id);
+                data_view.bulk_value = value;
+            }
+
+            return Ok(data_view_response);
+        }
+
+        // the cells of the inline grid edit their row as the instance of the child
+        let re = regex::Regex::new(r"^table-row-col-(?P<parent>[\w_]+)-(?P<name>[\w_]+)-(?P<field_name>[a-zA-Z][\w_]*)-(?P<index>\d+)$")?;
+
+        if let Some(cap) = re.captures(target) {
+            let mut element_id = HtmlElementId::new_with_regex(&cap)?;
+            // the index is the row, not a flag or list item of the field
+            let index = element_id.index.take().context("Missing row index")?;
+            let element_id = &element_id;
+            let field_name = element_id.field_name.clone().context("Missing field_name")?;
+            let data_view = data_view_get_mut!(self, element_id);
+            data_view.select_row(index)?;
+
+            if data_view.evaluate_field_states().get(&field_name).is_some_and(|(_, disabled, _)| *disabled) {
+                Err(format!("Field {} of {} is read only", field_name, data_view.data_view_id.form_id))?;
+            }
+
+            // a new key would be saved as another row, leaving the stored one behind
+            if let Some(service) = self.server_connection.service_map.get(&data_view.data_view_id.schema_name) {
+                let is_stored = service.get_primary_key(&data_view.instance).is_some_and(|primary_key| service.find_pos(&primary_key).is_some());
+
+                if is_stored && service.primary_keys.contains(&field_name) {
+                    Err(format!("Field {} is the key of the stored row {} of {}", field_name, index + 1, data_view.data_view_id.form_id))?;
+                }
+            }
+
+            let data_view = data_view_get!(self, element_id);
+            let (value, _) = parse_value_process(data_view, &self.server_connection, element_id, value)?;
+            log::debug!(target: LOG_EVENT, "process_edit_target({}) : row {}, {} = {}", target, index, field_name, value);
+            let data_view_parent = data_view_get_parent_mut!(self, element_id);
+            data_view_parent.set_value(&self.server_connection, self.watcher.as_ref(), &field_name, &value, element_id)?;
+            data_view_parent.sync_grid(&self.server_connection, self.watcher.as_ref(), &element_id.data_view_id.schema_name)?;
+            return self.build_grid_response(element_id);
+        }
+
         let mut data_view_response = DataViewResponse { changes: json!({}), ..Default::default() };
-        let re = regex::Regex::new(r"(?P<form_type>instance|filter|aggregate|sort)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)-(?P<field_name>[\w_]+)(?P<form_type_ext>@min|@max)?(-(?P<index>\d+))?")?;
+        let re = regex::Regex::new(r"(?P<form_type>instance|filter|aggregate|sort)-((?P<parent>[\w_]+)-)?(?P<name>[\w_]+)-(?P<field_name>[a-zA-Z][\w_]*)(?P<form_type_ext>@min|@max)?(-(?P<index>\d+))?")?;
 
         if let Some(cap) = re.captures(target) {
             let element_id = &HtmlElementId::new_with_regex(&cap)?;
@@ -3625,10 +6134,54 @@ impl DataViewManager<'_> {
             };
 
             let data_view = data_view_get!(self, element_id);
-            let (value, is_flags) = parse_value_process(data_view, &self.server_connection, element_id, value)?;
+            let field = data_view.properties.get(field_name).and_then(|field| field.as_item());
+
+            if element_id.form_type == FormType::Instance && data_view.evaluate_field_states().get(field_name).is_some_and(|(_, disabled, _)| *disabled) {
+                Err(format!("Field {} of {} is read only", field_name, data_view.data_view_id.form_id))?;
+            }
+
+            // binary files are kept as data url until the record is saved, the upload is done by DataView::save
+            let file = match field.filter(|field| DataView::file_encoding(field) == Some("binary") && value.len() > 0) {
+                Some(field) => {
+                    let data_view_parent = data_view_get_parent!(self, element_id);
+
+                    if element_id.form_type != FormType::Instance || !matches!(data_view_parent.action, DataViewProcessAction::New | DataViewProcessAction::Edit) {
+                        Err(format!("{} can't be changed in {} mode", field_name, data_view_parent.action))?;
+                    }
+
+                    data_view_parent.check_access(&self.server_connection, DataView::method_from_action(&data_view_parent.action))?;
+
+                    let max_size = match &field.schema_kind {
+                        SchemaKind::Type(Type::String(typ)) => typ.max_length,
+                        _ => None,
+                    };
+
+                    field.schema_data.extensions.get("x-uploadPath").and_then(|path| path.as_str()).context(format!("Missing x-uploadPath in field {}", field_name))?;
+                    let data = base64::engine::general_purpose::STANDARD.decode(value)?;
+
+                    if max_size.is_some_and(|max_size| data.len() > max_size) {
+                        Err(format!("File of {} exceeds the maximum size of {} bytes", field_name, max_size.unwrap_or_default()))?;
+                    }
+
+                    let media_type = DataView::file_media_type(field).filter(|media_type| !media_type.contains('*')).unwrap_or("application/octet-stream".to_string());
+                    Some(json!(format!("data:{};base64,{}", media_type, value)))
+                }
+                None => None,
+            };
+
+            let data_view = data_view_get!(self, element_id);
+
+            let (value, is_flags) = match file {
+                Some(file) => (file, false),
+                None => parse_value_process(data_view, &self.server_connection, element_id, value)?,
+            };
+            log::debug!(target: LOG_EVENT, "process_edit_target({}) : {} = {}", target, field_name, value);
             let data_view_parent = data_view_get_parent_mut!(self, element_id);
             data_view_parent.set_value(&self.server_connection, self.watcher.as_ref(), field_name, &value, element_id)?;
             data_view_parent.build_changes(element_id, &mut data_view_response.changes)?;
+            data_view_parent.build_states(&mut data_view_response.states);
+            let data_view = data_view_get!(self, element_id);
+            data_view_response.options = DataView::build_options(self, data_view)?;
 
             if is_flags {
                 let data_view = data_view_get!(self, element_id);
@@ -3641,6 +6194,11 @@ impl DataViewManager<'_> {
             return Ok(data_view_response);
         }
 
+        if target == "group_owner-active" {
+            self.switch_group_owner(value.parse::<u64>()?)?;
+            return Ok(data_view_response);
+        }
+
         let re = regex::Regex::new(r"login-(?P<name>[\w_]+)")?;
 
         for cap in re.captures_iter(target) {
@@ -3663,9 +6221,18 @@ impl DataViewManager<'_> {
         }
 
         let params = serde_json::from_value::<EventIn>(params)?;
+        log::info!(target: LOG_EVENT, "{} : {}", params.event, params.form_id);
 
         let data_view_response = if params.event == "OnClick" {
             self.process_click_target(&params.form_id).await?
+        } else if params.event == "OnInput" {
+            let mut ret = DataViewResponse { ..Default::default() };
+
+            for (target, value) in params.data.as_object().context("Param 'data' is not object ")? {
+                ret = self.process_typeahead_target(target, value.as_str().context("not string")?).await?;
+            }
+
+            ret
         } else {
             let mut ret = DataViewResponse { ..Default::default() };
 
@@ -3688,12 +6255,39 @@ pub struct DataViewManagerWrapper<'a> {
     pub data_view_manager: DataViewManager<'a>,
 }
 
+#[cfg(target_arch = "wasm32")]
+struct JsCallbackPartial {
+    function: js_sys::Function,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl CallbackPartial for JsCallbackPartial {
+    fn on_progress(&self, progress: &LoginProgress) {
+        let progress = match serde_wasm_bindgen::to_value(progress) {
+            Ok(progress) => progress,
+            Err(err) => {
+                log::error!(target: LOG_EVENT, "login progress of {} : {}", progress.schema_name, err);
+                return;
+            }
+        };
+
+        if let Err(err) = self.function.call1(&JsValue::NULL, &progress) {
+            log::error!(target: LOG_EVENT, "login progress callback : {:?}", err);
+        }
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
 impl DataViewManagerWrapper<'_> {
-    pub async fn login(&mut self, params: JsValue) -> Result<JsValue, JsValue> {
+    pub async fn login(&mut self, params: JsValue, callback_partial: Option<js_sys::Function>) -> Result<JsValue, JsValue> {
         let params = serde_wasm_bindgen::from_value::<Value>(params)?;
+        // production builds only report the warnings, log_level (error, warn, info, debug, trace) raises it
+        let level = params.get("log_level").and_then(|level| level.as_str()).and_then(|level| level.parse().ok()).unwrap_or(log::LevelFilter::Warn);
+        init_logger(level);
+        let callback_partial = callback_partial.map(|function| JsCallbackPartial { function });
+        let callback_partial = callback_partial.as_ref().map(|callback_partial| callback_partial as &dyn CallbackPartial);
 
-        let ret = match self.data_view_manager.login(params).await {
+        let ret = match self.data_view_manager.login(params, callback_partial).await {
             Ok(ret) => ret,
             Err(err) => return Err(JsValue::from_str(&err.to_string())),
         };
@@ -3701,6 +6295,19 @@ impl DataViewManagerWrapper<'_> {
         Ok(serde_wasm_bindgen::to_value(&ret)?)
     }
 
+    // texts of the page before the login, exported as a static method of the js class
+    pub fn labels(locale: JsValue) -> Result<JsValue, JsValue> {
+        let locale = serde_wasm_bindgen::from_value::<i18n::Locale>(locale)?;
+        Ok(serde_wasm_bindgen::to_value(&locale.labels())?)
+    }
+
+    // changes the level of the console log after the login, also a static method of the js class
+    pub fn set_log_level(level: &str) -> Result<(), JsValue> {
+        let level = level.parse::<log::LevelFilter>().map_err(|err| JsValue::from_str(&format!("Invalid log level {} : {}", level, err)))?;
+        init_logger(level);
+        Ok(())
+    }
+
     pub async fn process(&mut self, params: JsValue) -> Result<JsValue, JsValue> {
         let params = serde_wasm_bindgen::from_value::<Value>(params)?;
 
@@ -3713,12 +6320,284 @@ impl DataViewManagerWrapper<'_> {
     }
 }
 
+#[cfg(test)]
+mod tests_grid {
+    use super::*;
+
+    fn request_with_grid(properties: Value, rows: Vec<Value>) -> DataView {
+        let mut data_view = DataView::new("/request", DataViewType::Primary, None, DataViewProcessAction::Edit);
+        let mut child = DataView::new("/requestProduct", DataViewType::Dependent, Some("request"), DataViewProcessAction::New);
+        child.properties = serde_json::from_value(properties).unwrap();
+        child.filter_results = rows;
+        data_view.childs.push(child);
+        data_view
+    }
+
+    #[test]
+    fn check_grid_rows_by_cell() {
+        let properties = json!({
+            "request": {"type": "integer", "x-essential": true, "x-$ref": "#/components/schemas/request"},
+            "product": {"type": "integer", "x-essential": true},
+            "code": {"type": "string", "maxLength": 3, "pattern": "^[A-Z]+$"},
+            "quantity": {"type": "number"},
+        });
+
+        let rows = vec![
+            json!({"product": 1, "code": "AB", "quantity": 1.5}),
+            json!({"product": null, "code": "ABCD", "quantity": "x"}),
+            json!({"product": 1.5, "code": "ab"}),
+        ];
+
+        let data_view = request_with_grid(properties, rows);
+        let mut errors = data_view.check_grid_rows().into_iter().map(|result| (result.index, result.error.unwrap_or_default())).collect::<Vec<_>>();
+        errors.sort();
+
+        assert_eq!(
+            errors,
+            vec![
+                (Some(1), "Missing value of product in row 2".to_string()),
+                (Some(1), "Value of code in row 2 exceeds the maximum length of 3".to_string()),
+                (Some(1), "Value of quantity in row 2 isn't a number".to_string()),
+                (Some(2), "Value of code in row 3 don't match the pattern ^[A-Z]+$".to_string()),
+                (Some(2), "Value of product in row 3 isn't an integer".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_cell_constraints() {
+        let code = serde_json::from_value::<Schema>(json!({"type": "string", "minLength": 2, "enum": ["AB", "ABC"]})).unwrap();
+        let quantity = serde_json::from_value::<Schema>(json!({"type": "integer", "minimum": 1, "maximum": 10})).unwrap();
+        let rate = serde_json::from_value::<Schema>(json!({"type": "number", "minimum": 0.0, "exclusiveMinimum": true})).unwrap();
+
+        assert_eq!(DataView::check_cell(&code, &json!("A")), Some("is shorter than the minimum length of 2".to_string()));
+        assert_eq!(DataView::check_cell(&code, &json!("AC")), Some("isn't one of AB, ABC".to_string()));
+        assert_eq!(DataView::check_cell(&code, &json!("ABC")), None);
+        assert_eq!(DataView::check_cell(&quantity, &json!(0)), Some("is less than the minimum of 1".to_string()));
+        assert_eq!(DataView::check_cell(&quantity, &json!(11)), Some("is greater than the maximum of 10".to_string()));
+        assert_eq!(DataView::check_cell(&quantity, &json!(10)), None);
+        assert_eq!(DataView::check_cell(&rate, &json!(0.0)), Some("is less than the minimum of 0".to_string()));
+        assert_eq!(DataView::check_cell(&rate, &json!(0.5)), None);
+    }
+
+    #[test]
+    fn undo_of_grid_writes() {
+        let stored = json!({"request": 1, "product": 2, "quantity": 3});
+        let primary_key = json!({"request": 1, "product": 2});
+
+        match SaveUndo::of_write("requestProduct", "/request_product", Some(stored.clone()), Some(primary_key.clone())) {
+            Some(SaveUndo::Updated(path, value)) => assert_eq!((path.as_str(), value), ("/request_product", stored.clone())),
+            undo => panic!("unexpected {:?}", undo),
+        }
+
+        match SaveUndo::of_write("requestProduct", "/request_product", None, Some(primary_key.clone())) {
+            Some(SaveUndo::Inserted(schema_name, value)) => assert_eq!((schema_name.as_str(), value), ("requestProduct", primary_key)),
+            undo => panic!("unexpected {:?}", undo),
+        }
+
+        assert!(SaveUndo::of_write("requestProduct", "/request_product", None, None).is_none());
+        assert!(matches!(SaveUndo::of_removal("/request_product", stored.clone(), false), SaveUndo::Removed(..)));
+        // the soft deleted row is restored by writing back the marker
+        assert!(matches!(SaveUndo::of_removal("/request_product", stored, true), SaveUndo::Updated(..)));
+    }
+}
+
+#[cfg(test)]
+mod tests_remove {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    struct NoWatch;
+
+    impl DataViewWatch for NoWatch {
+        fn check_set_value(&self, _: &mut DataView, _: Option<&str>, _: &ServerConnection, _: &str, _: &Value, _: &HtmlElementId) -> Result<bool, Box<dyn std::error::Error>> {
+            Ok(true)
+        }
+
+        fn check_save(&self, _: &mut DataView, _: Option<&str>, _: &ServerConnection, _: &HtmlElementId) -> Result<(bool, DataViewProcessAction), Box<dyn std::error::Error>> {
+            Ok((true, DataViewProcessAction::Edit))
+        }
+
+        fn menu(&self) -> Value {
+            json!({})
+        }
+    }
+
+    fn service(schema_name: &str, path: &str, primary_keys: &[&str], list: Vec<Value>) -> Service {
+        let list_str = list.iter().map(|item| item.to_string()).collect();
+
+        Service {
+            path: path.to_string(),
+            schema_name: schema_name.to_string(),
+            primary_keys: primary_keys.iter().map(|name| name.to_string()).collect(),
+            short_description_list: vec![],
+            list,
+            list_str,
+            list_hidden: vec![],
+            list_str_hidden: vec![],
+            soft_delete: None,
+            list_deleted: vec![],
+            list_str_deleted: vec![],
+            loaded: true,
+        }
+    }
+
+    fn new_server_connection(url: &str) -> ServerConnection {
+        let mut server_connection = ServerConnection::new(url);
+
+        server_connection.login_response.openapi = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": {"title": "test", "version": "1"},
+            "paths": {},
+            "components": {"schemas": {
+                "request": {"type": "object", "properties": {
+                    "id": {"type": "integer", "x-primaryKey": true},
+                }},
+                "requestProduct": {"type": "object", "properties": {
+                    "request": {"type": "integer", "x-primaryKey": true, "x-$ref": "#/components/schemas/request", "x-onDelete": "cascade"},
+                    "product": {"type": "integer", "x-primaryKey": true},
+                }},
+                "requestPayment": {"type": "object", "properties": {
+                    "id": {"type": "integer", "x-primaryKey": true},
+                    "request": {"type": "integer", "x-$ref": "#/components/schemas/request"},
+                }},
+            }},
+        }))
+        .unwrap();
+
+        for path in ["/request", "/request_product", "/request_payment"] {
+            let role = serde_json::from_value(json!({"path": path, "mask": 0xff})).unwrap();
+            server_connection.login_response.roles.push(role);
+        }
+
+        let rows = vec![json!({"request": 1, "product": 1, "quantity": 1}), json!({"request": 1, "product": 2, "quantity": 2})];
+        server_connection.service_map.insert("request".to_string(), service("request", "/request", &["id"], vec![json!({"id": 1}), json!({"id": 2})]));
+        server_connection.service_map.insert("requestProduct".to_string(), service("requestProduct", "/request_product", &["request", "product"], rows));
+        server_connection.service_map.insert("requestPayment".to_string(), service("requestPayment", "/request_payment", &["id"], vec![json!({"id": 1, "request": 2})]));
+        server_connection
+    }
+
+    fn new_data_view_manager(server_connection: ServerConnection) -> DataViewManager<'static> {
+        let watcher: &'static Box<dyn DataViewWatch> = Box::leak(Box::new(Box::new(NoWatch) as Box<dyn DataViewWatch>));
+        let mut data_view_manager = DataViewManager::new("http://localhost", watcher);
+        data_view_manager.server_connection = server_connection;
+        data_view_manager
+    }
+
+    #[test]
+    fn delete_dependents_before_the_record() {
+        let data_view_manager = new_data_view_manager(new_server_connection("http://localhost"));
+        let mut counts = vec![];
+        let mut removals = vec![];
+        data_view_manager.delete_dependents("request", &json!({"id": 1}), &mut counts, &mut removals).unwrap();
+        assert_eq!(counts, vec![("requestProduct".to_string(), 2)]);
+
+        assert_eq!(
+            removals,
+            vec![
+                ("requestProduct".to_string(), json!({"request": 1, "product": 1})),
+                ("requestProduct".to_string(), json!({"request": 1, "product": 2})),
+            ]
+        );
+
+        // the payment of the request 2 hasn't x-onDelete, so it restricts the removal
+        let mut removals = vec![];
+        assert!(data_view_manager.delete_dependents("request", &json!({"id": 2}), &mut vec![], &mut removals).is_err());
+    }
+
+    #[test]
+    fn delete_dependents_of_unchecked_and_soft_deleted() {
+        let mut server_connection = new_server_connection("http://localhost");
+        server_connection.service_map.get_mut("requestProduct").unwrap().loaded = false;
+        let data_view_manager = new_data_view_manager(server_connection);
+        // rows that aren't in cache can't be cascaded
+        assert!(data_view_manager.delete_dependents("request", &json!({"id": 1}), &mut vec![], &mut vec![]).is_err());
+
+        let mut server_connection = new_server_connection("http://localhost");
+        server_connection.service_map.get_mut("request").unwrap().soft_delete = Some("deletedAt".to_string());
+        let data_view_manager = new_data_view_manager(server_connection);
+        let mut removals = vec![];
+        // the soft deleted record keeps its dependents
+        data_view_manager.delete_dependents("request", &json!({"id": 2}), &mut vec![], &mut removals).unwrap();
+        assert!(removals.is_empty());
+    }
+
+    // answers each request with the next response, returning the request lines
+    fn serve(responses: Vec<(u16, Value)>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            let mut requests = vec![];
+
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                requests.push(line.trim().to_string());
+                let mut content_length = 0;
+
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+
+                    if header.trim().is_empty() {
+                        break;
+                    }
+
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+
+                let mut data = vec![0; content_length];
+                reader.read_exact(&mut data).unwrap();
+                let body = body.to_string();
+                let response = format!("HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body);
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+
+            requests
+        });
+
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn remove_rows_rolls_back_on_failure() {
+        let stored = json!({"request": 1, "product": 1, "quantity": 1});
+        let (url, handle) = serve(vec![(200, stored.clone()), (500, json!("locked")), (200, stored.clone())]);
+        let mut server_connection = new_server_connection(&url);
+
+        let removals = vec![
+            ("requestProduct".to_string(), json!({"request": 1, "product": 1})),
+            ("requestProduct".to_string(), json!({"request": 1, "product": 2})),
+            ("request".to_string(), json!({"id": 1})),
+        ];
+
+        let results = server_connection.remove_rows("request", removals).await.unwrap();
+        let requests = handle.join().unwrap();
+        // the record isn't touched and the removed row is put back with its key
+        assert_eq!(requests.iter().map(|line| line.split(' ').next().unwrap_or_default()).collect::<Vec<&str>>(), vec!["DELETE", "DELETE", "POST"]);
+        assert!(requests[2].starts_with("POST /rest/request_product "));
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].error, Some(r#"rolled back after failure in requestProduct {"product":2,"request":1}"#.to_string()));
+        assert!(results[1].error.as_ref().is_some_and(|error| error.starts_with(r#"requestProduct {"product":2,"request":1} : "#)));
+        let service = server_connection.service_map.get("requestProduct").unwrap();
+        assert_eq!(service.find_one(&json!({"request": 1, "product": 1})), Some(&stored));
+        assert_eq!(service.list.len(), 2);
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(feature = "test-selelium")]
 pub mod tests {
     use crate::HtmlElementId;
     use crate::ServerConnection;
-    use crate::{DataViewManager, DataViewProcessParams, DataViewWatch};
+    use crate::{DataViewManager, DataViewProcessParams, DataViewType, DataViewWatch};
     use anyhow::{anyhow, Context};
     use rufs_base_rust::data_store::Filter;
     use serde::Deserialize;
@@ -3790,6 +6669,8 @@ pub mod tests {
                     match command.command.as_str() {
                         "open" => {
                             data_view_manager.data_view_map.clear();
+                            data_view_manager.navigation.clear();
+                            data_view_manager.navigation_index = 0;
                             data_view_manager.server_connection = ServerConnection::new("http://localhost:8080");
                             continue;
                         }
@@ -3814,7 +6695,7 @@ pub mod tests {
                                             .iter()
                                             .find(|command| ["type", "sendKeys"].contains(&command.command.as_str()) && command.target == "id=login-password")
                                         {
-                                            match data_view_manager.server_connection.login("/login", &user.value, &password.value).await {
+                                            match data_view_manager.server_connection.login("/login", &user.value, &password.value, None).await {
                                                 Ok(_) => target = format!("#!/app/{}", data_view_manager.server_connection.login_response.path),
                                                 Err(err) => {
                                                     if let Some(http_msg) = test.commands.iter().find(|command| command.command == "assertText" && command.target == "id=http-error") {
@@ -3836,7 +6717,12 @@ pub mod tests {
                                 _ => {}
                             }
 
-                            let _res = data_view_manager.process_click_target(&target).await?;
+                            let res = data_view_manager.process_click_target(&target).await?;
+
+                            // the recorded clicks accept the confirmations
+                            if let Some(target) = res.confirm.get("target").and_then(|target| target.as_str()) {
+                                data_view_manager.process_click_target(target).await?;
+                            }
                         }
                         "type" | "sendKeys" | "select" => {
                             let value = if command.value.starts_with("label=") { &command.value[6..] } else { &command.value };
@@ -3868,7 +6754,7 @@ pub mod tests {
                             let data_view = data_view_get_mut!(data_view_manager, element_id);
 
                             let str = if let Some(index) = cap.name("index") {
-                                let list = if data_view.path.is_none() || data_view.filter_results.len() > 0 {
+                                let list = if data_view.path.is_none() || data_view.typ == DataViewType::Dependent || data_view.filter_results.len() > 0 {
                                     &data_view.filter_results
                                 } else {
                                     let service = data_view_manager
@@ -0,0 +1,221 @@
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::i18n::Locale;
+
+// Display and entry masks of the schema : x-mask is a pattern where 0 is a digit, A is a letter and * is any of both,
+// the other chars are literals. x-format names one of the formats below, used when x-mask is absent.
+const NAMED_MASKS: [(&str, &str); 4] = [("cpf", "000.000.000-00"), ("cnpj", "00.000.000/0000-00"), ("cep", "00000-000"), ("phone", "(00) 0000-0000")];
+// phones with the ninth digit
+const MASK_MOBILE: &str = "(00) 00000-0000";
+
+fn get_mask(extensions: &IndexMap<String, Value>) -> Option<&str> {
+    if let Some(mask) = extensions.get("x-mask").and_then(|mask| mask.as_str()) {
+        return Some(mask);
+    }
+
+    let format = extensions.get("x-format").and_then(|format| format.as_str())?;
+    NAMED_MASKS.iter().find(|(name, _)| *name == format).map(|(_, mask)| *mask)
+}
+
+fn get_number_format(extensions: &IndexMap<String, Value>) -> Option<&str> {
+    extensions.get("x-format").and_then(|format| format.as_str()).filter(|format| ["currency", "percent"].contains(format))
+}
+
+fn apply_mask(mask: &str, raw: &str) -> Option<String> {
+    let mut chars = raw.chars().filter(|c| c.is_alphanumeric()).peekable();
+    let mut out = String::new();
+
+    for m in mask.chars() {
+        let Some(c) = chars.peek() else {
+            break;
+        };
+
+        let accept = match m {
+            '0' => c.is_ascii_digit(),
+            'A' => c.is_alphabetic(),
+            '*' => true,
+            _ => {
+                out.push(m);
+                continue;
+            }
+        };
+
+        if !accept {
+            return None;
+        }
+
+        out.push(*c);
+        chars.next();
+    }
+
+    // values that don't fit the mask are shown as they are
+    if chars.peek().is_some() {
+        return None;
+    }
+
+    Some(out)
+}
+
+// 1234567.891 -> 1.234.567,89 in pt-BR, 1,234,567.89 in en
+fn format_decimal(value: f64, scale: usize, locale: &Locale) -> String {
+    let (thousands, decimal) = locale.number_separators();
+    let str = format!("{:.*}", scale, value.abs());
+    let (int_part, dec_part) = str.split_once('.').unwrap_or((&str, ""));
+    let mut groups = vec![];
+    let digits = int_part.chars().collect::<Vec<char>>();
+
+    for chunk in digits.rchunks(3).rev() {
+        groups.push(chunk.iter().collect::<String>());
+    }
+
+    let sign = if value < 0.0 { "-" } else { "" };
+
+    let int_part = groups.join(&thousands.to_string());
+
+    if dec_part.is_empty() {
+        format!("{}{}", sign, int_part)
+    } else {
+        format!("{}{}{}{}", sign, int_part, decimal, dec_part)
+    }
+}
+
+pub fn is_number_format(extensions: &IndexMap<String, Value>) -> bool {
+    get_number_format(extensions).is_some()
+}
+
+pub fn has_mask(extensions: &IndexMap<String, Value>) -> bool {
+    get_mask(extensions).is_some() || get_number_format(extensions).is_some()
+}
+
+// room for the literals of the mask, or for the prefix and separators of the number
+pub fn max_length(extensions: &IndexMap<String, Value>, max_length: usize) -> usize {
+    match get_mask(extensions) {
+        // the named phone mask grows with the ninth digit
+        Some(mask) if extensions.get("x-mask").is_none() && mask == NAMED_MASKS[3].1 => MASK_MOBILE.chars().count(),
+        Some(mask) => mask.chars().count(),
+        None => max_length + max_length / 3 + 5,
+    }
+}
+
+// the pattern shown as placeholder of the input
+pub fn placeholder(extensions: &IndexMap<String, Value>, locale: &Locale) -> Option<String> {
+    if let Some(mask) = get_mask(extensions) {
+        return Some(mask.replace(['0', 'A', '*'], "_"));
+    }
+
+    let (_, decimal) = locale.number_separators();
+
+    match get_number_format(extensions)? {
+        "currency" => Some(format!("{} 0{}00", locale.currency_symbol(), decimal)),
+        _ => Some(format!("0{}00 %", decimal)),
+    }
+}
+
+pub fn format_value(extensions: &IndexMap<String, Value>, value: &Value, locale: &Locale) -> Option<String> {
+    if let Some(format) = get_number_format(extensions) {
+        let value = match value {
+            Value::Number(number) => number.as_f64()?,
+            Value::String(str) => str.parse::<f64>().ok()?,
+            _ => return None,
+        };
+
+        return match format {
+            "currency" => Some(format!("{} {}", locale.currency_symbol(), format_decimal(value, 2, locale))),
+            _ => Some(format!("{} %", format_decimal(value, 2, locale))),
+        };
+    }
+
+    let mask = get_mask(extensions)?;
+
+    let is_phone = extensions.get("x-mask").is_none() && extensions.get("x-format").and_then(|format| format.as_str()) == Some("phone");
+
+    let raw = match value {
+        Value::String(str) => str.clone(),
+        // numbers lose the leading zeros of documents like cpf, which are restored up to the digits of the mask
+        Value::Number(number) => {
+            let raw = number.as_u64()?.to_string();
+            let digits = mask.chars().filter(|c| *c == '0').count();
+            format!("{:0>digits$}", raw)
+        }
+        _ => return None,
+    };
+
+    if raw.is_empty() {
+        return None;
+    }
+
+    if is_phone && raw.chars().filter(|c| c.is_ascii_digit()).count() == 11 {
+        return apply_mask(MASK_MOBILE, &raw);
+    }
+
+    apply_mask(mask, &raw)
+}
+
+// removes the literals of the mask, and the thousands separators of the locale, leaving a decimal point
+pub fn strip_value(extensions: &IndexMap<String, Value>, value: &str, locale: &Locale) -> String {
+    if get_number_format(extensions).is_some() {
+        let (thousands, decimal) = locale.number_separators();
+        let value = value.replace(locale.currency_symbol(), "").replace(['%', ' ', thousands], "");
+        return value.replace(decimal, ".");
+    }
+
+    if get_mask(extensions).is_some() {
+        return value.chars().filter(|c| c.is_alphanumeric()).collect();
+    }
+
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn extensions(value: Value) -> IndexMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn formats_named_masks() {
+        let cpf = extensions(json!({"x-format": "cpf"}));
+        assert_eq!(format_value(&cpf, &json!("12345678901"), &Locale::PtBr).as_deref(), Some("123.456.789-01"));
+        // numbers keep the leading zeros
+        assert_eq!(format_value(&cpf, &json!(1234567890), &Locale::PtBr).as_deref(), Some("012.345.678-90"));
+        // values that don't fit are shown as they are
+        assert_eq!(format_value(&cpf, &json!("123456789012"), &Locale::PtBr), None);
+
+        let phone = extensions(json!({"x-format": "phone"}));
+        assert_eq!(format_value(&phone, &json!("1133334444"), &Locale::PtBr).as_deref(), Some("(11) 3333-4444"));
+        assert_eq!(format_value(&phone, &json!("11933334444"), &Locale::PtBr).as_deref(), Some("(11) 93333-4444"));
+        assert_eq!(max_length(&phone, 11), 15);
+        assert_eq!(placeholder(&phone, &Locale::PtBr).as_deref(), Some("(__) ____-____"));
+    }
+
+    #[test]
+    fn formats_numbers_by_locale() {
+        let currency = extensions(json!({"x-format": "currency"}));
+        assert_eq!(format_value(&currency, &json!(1234567.891), &Locale::PtBr).as_deref(), Some("R$ 1.234.567,89"));
+        assert_eq!(format_value(&currency, &json!(-1234.5), &Locale::En).as_deref(), Some("$ -1,234.50"));
+        assert_eq!(placeholder(&currency, &Locale::En).as_deref(), Some("$ 0.00"));
+
+        let percent = extensions(json!({"x-format": "percent"}));
+        assert_eq!(format_value(&percent, &json!("12.5"), &Locale::PtBr).as_deref(), Some("12,50 %"));
+        assert_eq!(placeholder(&percent, &Locale::PtBr).as_deref(), Some("0,00 %"));
+    }
+
+    #[test]
+    fn strips_values() {
+        let currency = extensions(json!({"x-format": "currency"}));
+        assert_eq!(strip_value(&currency, "R$ 1.234.567,89", &Locale::PtBr), "1234567.89");
+        assert_eq!(strip_value(&currency, "1.234", &Locale::PtBr), "1234");
+        assert_eq!(strip_value(&currency, "$ 1,234.5", &Locale::En), "1234.5");
+
+        let cep = extensions(json!({"x-format": "cep"}));
+        assert_eq!(strip_value(&cep, "01234-567", &Locale::PtBr), "01234567");
+
+        let plain = extensions(json!({}));
+        assert_eq!(strip_value(&plain, "1.234", &Locale::PtBr), "1.234");
+        assert!(!has_mask(&plain));
+    }
+}
@@ -0,0 +1,1103 @@
+use indexmap::IndexMap;
+
+use crate::FormType;
+
+// Neutral description of the forms and tables produced by DataView.
+// Element ids and names are the event protocol with DataViewManager::process, renderers must keep them
+// and derive the secondary ones with the documented suffixes.
+
+#[derive(Debug, Clone, Default)]
+pub struct ActionView {
+    pub id: String,
+    pub name: String,
+    pub label: String,
+    pub icon: String,
+    pub primary: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceView {
+    // reference-view-{form_id}-{field_name}
+    pub view_id: String,
+    // reference-create-{form_id}-{field_name}
+    pub create_id: Option<String>,
+    // reference-search-{form_id}-{field_name}
+    pub search_id: Option<String>,
+}
+
+// byte keeps the file base64 encoded in the field, binary keeps the reference returned by the upload
+#[derive(Debug, Clone, Default)]
+pub struct FileView {
+    pub encoding: String,
+    pub media_type: Option<String>,
+    pub max_size: Option<usize>,
+    // data url or link of the current value, shown in {id}-preview
+    pub href: Option<String>,
+    pub download_label: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FieldView {
+    // {form_type}-{form_id}-{field_name}, flags append -{index}, filter ranges append -range, @min and @max,
+    // sort appends -order_by, -index and -table_visible
+    pub id: String,
+    pub name: String,
+    pub label: String,
+    // description of the field, shown as help text and tooltip
+    pub help: Option<String>,
+    // text, number, date, datetime-local or checkbox
+    pub input_type: String,
+    pub step: Option<String>,
+    pub pattern: Option<String>,
+    pub max_length: usize,
+    pub col_size: usize,
+    pub rangeable: bool,
+    pub required: bool,
+    pub disabled: bool,
+    // from x-visibleIf, the field stays in the form to be shown again when the rule changes
+    pub hidden: bool,
+    // short option lists are shown as select, long ones as suggestions of the input
+    pub select: bool,
+    // the suggestions of long reference lists come from the OnInput event, instead of being embedded
+    pub typeahead: bool,
+    pub options: Vec<String>,
+    pub reference: Option<ReferenceView>,
+    pub flags: Vec<String>,
+    // (value, label) of the date ranges in filter and of the periods in aggregate
+    pub range_options: Vec<(String, String)>,
+    // label between the @min and @max inputs
+    pub range_to: String,
+    // layout of instance forms, from x-tab, x-group and x-row
+    pub tab: Option<String>,
+    pub group: Option<String>,
+    pub row: Option<usize>,
+    // values of an array of scalars, each one in {id}-{index}, with list_add-{id}, list_remove-{id}-{index},
+    // list_up-{id}-{index} and list_down-{id}-{index}
+    pub list_items: Option<Vec<String>>,
+    pub file: Option<FileView>,
+    // pattern of x-mask or x-format
+    pub placeholder: Option<String>,
+    // value embedded in the control by the cells of the inline grid, the forms receive theirs as changes
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FormView {
+    pub form_type: FormType,
+    pub form_id: String,
+    // {form_type}-{form_id}, the container is div-{id}
+    pub id: String,
+    pub label: String,
+    pub hidden: bool,
+    // nested object of the parent instance, rendered as a fieldset inside the parent form
+    pub embedded: bool,
+    // create-{id}
+    pub href_new: Option<String>,
+    pub fields: Vec<FieldView>,
+    pub actions: Vec<ActionView>,
+    pub childs: Vec<FormView>,
+    // the search forms of instance, the table of an instance form is loaded in div-table-{form_id}
+    pub filter: Option<Box<FormView>>,
+    pub aggregate: Option<Box<FormView>>,
+    pub sort: Option<Box<FormView>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ColumnView {
+    // sort_left-{form_id}-{field_name}, sort_toggle-{form_id}-{field_name} and sort_rigth-{form_id}-{field_name}
+    pub field_name: String,
+    pub label: String,
+    pub help: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CellView {
+    // table-row-col-{form_id}-{field_name}-{index}
+    pub field_name: String,
+    pub href: String,
+    pub text: String,
+    // the href is a file to be saved
+    pub download: bool,
+    // input of the inline grid, with the id of the cell
+    pub control: Option<FieldView>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RowView {
+    // table-row-view-{form_id}-{index}, table-row-edit-{form_id}-{index}
+    pub index: usize,
+    pub href_view: String,
+    pub href_edit: Option<String>,
+    // search_select-{form_id}-{select_out}-{item_index}
+    pub select_out: Option<String>,
+    pub item_index: usize,
+    // table-row-remove-{form_id}-{index}, table-row-up-{form_id}-{index} and table-row-down-{form_id}-{index}
+    pub movable: bool,
+    // bulk_select-{form_id}-{index}, None when the rows of the table aren't selectable
+    pub selected: Option<bool>,
+    // table-row-clone-{form_id}-{index}
+    pub clonable: bool,
+    // table-row-restore-{form_id}-{index}, for the rows of the trash
+    pub restorable: bool,
+    pub cells: Vec<CellView>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PaginationView {
+    // selected_page-{form_id}-{page} and page_size-{form_id}
+    pub pages: Vec<usize>,
+    pub page_size: usize,
+}
+
+// translated labels of the table links
+#[derive(Debug, Clone, Default)]
+pub struct TableLabels {
+    pub view: String,
+    pub edit: String,
+    pub clone: String,
+    pub select: String,
+    pub remove: String,
+    pub up: String,
+    pub down: String,
+    pub add: String,
+    pub page_size: String,
+    pub selected: String,
+    pub select_all: String,
+    pub select_none: String,
+    pub bulk_remove: String,
+    pub bulk_update: String,
+    pub bulk_export: String,
+    pub bulk_field: String,
+    pub bulk_value: String,
+    pub trash: String,
+    pub trash_exit: String,
+    pub restore: String,
+}
+
+// actions over the selected rows : bulk_select_all-{form_id}, bulk_select_none-{form_id}, bulk_export-{form_id},
+// bulk_remove-{form_id} and bulk_update-{form_id}, that sets the field of bulk_field-{form_id} to bulk_value-{form_id}
+#[derive(Debug, Clone, Default)]
+pub struct BulkView {
+    pub selected: usize,
+    // rows of the filter, taken by bulk_select_all
+    pub matching: usize,
+    pub removable: bool,
+    pub updatable: bool,
+    // (name, label) of the fields accepted by the bulk update
+    pub fields: Vec<(String, String)>,
+    pub field: Option<String>,
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TableView {
+    // table-{form_id}
+    pub form_id: String,
+    pub columns: Vec<ColumnView>,
+    pub rows: Vec<RowView>,
+    pub pagination: Option<PaginationView>,
+    pub labels: TableLabels,
+    // rows edited inline, with table-row-add-{form_id}
+    pub editable: bool,
+    pub bulk: Option<BulkView>,
+    // trash-{form_id} switches to the soft deleted rows and back, None without x-softDelete
+    pub trash: Option<bool>,
+}
+
+// navigation-{index} of each entry up to the current one
+#[derive(Debug, Clone, Default)]
+pub struct NavigationItemView {
+    pub index: usize,
+    pub label: String,
+    pub active: bool,
+}
+
+// content of #navigation, with navigation-back and navigation-forward when there are entries to go
+#[derive(Debug, Clone, Default)]
+pub struct NavigationView {
+    pub items: Vec<NavigationItemView>,
+    pub back: Option<String>,
+    pub forward: Option<String>,
+}
+
+pub trait DataViewRenderer: std::marker::Sync + Send {
+    fn render_form(&self, form: &FormView) -> String;
+    fn render_table(&self, table: &TableView) -> String;
+    // content of div-list-{id}, replaced when items are added, removed or moved
+    fn render_list(&self, field: &FieldView) -> String;
+    // content of div-control-{id}, replaced when the options of a cascading reference are narrowed
+    fn render_field_control(&self, field: &FieldView) -> String;
+    fn render_navigation(&self, navigation: &NavigationView) -> String;
+}
+
+#[derive(Default)]
+pub struct BootstrapRenderer {}
+
+impl BootstrapRenderer {
+    fn render_title(help: &Option<String>) -> String {
+        match help {
+            Some(help) => format!(r#"title="{}""#, help.replace('"', "&quot;")),
+            None => "".to_string(),
+        }
+    }
+
+    fn render_options(options: &Vec<String>) -> String {
+        let mut html_options = vec![];
+
+        for str in options {
+            html_options.push(format!(r##"<option value="{str}">{str}</option>"##));
+        }
+
+        html_options.join("\n")
+    }
+
+    fn render_range_options(options: &Vec<(String, String)>) -> String {
+        let mut html_options = vec![];
+
+        for (value, label) in options {
+            html_options.push(format!(r##"<option value="{value}">{label}</option>"##));
+        }
+
+        html_options.join("\n")
+    }
+
+    fn render_file(field: &FieldView, file: &FileView) -> String {
+        let id = &field.id;
+        let name = &field.name;
+        let encoding = &file.encoding;
+        let media_type = file.media_type.clone().unwrap_or_default();
+        let accept = file.media_type.as_ref().map(|media_type| format!(r#"accept="{}""#, media_type)).unwrap_or_default();
+        let max_size = file.max_size.map(|max_size| format!(r#"data-max-size="{}""#, max_size)).unwrap_or_default();
+        let required = if field.required { "required" } else { "" };
+        // the reference comes from the server, so it is escaped before it goes in the attribute
+        let href = file.href.clone().unwrap_or_default().replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;");
+        let hidden = if file.href.is_some() { "" } else { "hidden" };
+        let download_label = &file.download_label;
+
+        let html_preview = if media_type.starts_with("image/") {
+            format!(r#"<img id="{id}-preview" src="{href}" class="img-thumbnail" style="max-height: 10em" {hidden}>"#)
+        } else {
+            format!(r#"<a id="{id}-preview" href="{href}" download {hidden}><i class="bi bi-download"></i> {download_label}</a>"#)
+        };
+
+        let html_input = if field.disabled {
+            "".to_string()
+        } else {
+            format!(r#"<input class="form-control" id="{id}" name="{name}" type="file" {accept} {max_size} data-encoding="{encoding}" data-media-type="{media_type}" {required}>"#)
+        };
+
+        format!(
+            r##"
+                        {html_input}
+                        {html_preview}
+                        "##
+        )
+    }
+
+    fn render_control(field: &FieldView) -> String {
+        let id = &field.id;
+        let name = &field.name;
+        let html_options = BootstrapRenderer::render_options(&field.options);
+        let required = if field.required { "required" } else { "" };
+        let disabled = if field.disabled { "disabled" } else { "" };
+
+        let html_options = match &field.value {
+            Some(value) if field.select => html_options.replace(&format!(r#"<option value="{value}">"#), &format!(r#"<option value="{value}" selected>"#)),
+            _ => html_options,
+        };
+
+        let value = match &field.value {
+            Some(value) if field.input_type == "checkbox" => if value == "true" { "checked".to_string() } else { "".to_string() },
+            Some(value) => format!(r#"value="{}""#, value.replace('"', "&quot;")),
+            None => "".to_string(),
+        };
+
+        if let Some(file) = &field.file {
+            BootstrapRenderer::render_file(field, file)
+        } else if field.select {
+            format!(
+                r##"
+                        <select class="form-select" id="{id}" name="{name}" {required} {disabled}>
+                            <option value=""></option>
+                            {html_options}
+                        </select>
+                        "##
+            )
+        } else {
+            let input_type = &field.input_type;
+            let step = field.step.as_ref().map(|step| format!(r#"step="{}""#, step)).unwrap_or_default();
+            let pattern = field.pattern.as_ref().map(|pattern| format!(r#"pattern="{}""#, pattern)).unwrap_or_default();
+            let max_length = field.max_length;
+            let placeholder = field.placeholder.clone().unwrap_or_default();
+            let list_id = format!("list-{}", id);
+
+            let typeahead = if field.typeahead { r#"data-typeahead="true""# } else { "" };
+
+            let html_datalist = if field.options.len() > 0 || field.typeahead {
+                format!(
+                    r##"
+                        <datalist id="{list_id}">
+                            {html_options}
+                        </datalist>
+                        "##
+                )
+            } else {
+                "".to_string()
+            };
+
+            format!(
+                r##"
+                        <input class="form-control" id="{id}" name="{name}" type="{input_type}" {step} {pattern} maxlength="{max_length}" placeholder="{placeholder}" {value} {required} {disabled} list="{list_id}" autocomplete="off" {typeahead}>
+                        {html_datalist}
+                        "##
+            )
+        }
+    }
+
+    fn render_bulk(form_id: &str, bulk: &BulkView, labels: &TableLabels) -> String {
+        let selected = bulk.selected;
+        let matching = bulk.matching;
+
+        let html_remove = if bulk.removable {
+            format!(r##"<a href id="bulk_remove-{form_id}" class="btn btn-sm btn-danger"><i class="bi bi-trash"></i> {}</a>"##, labels.bulk_remove)
+        } else {
+            "".to_string()
+        };
+
+        let html_update = if bulk.updatable {
+            let mut html_fields = vec![r#"<option value=""></option>"#.to_string()];
+
+            for (name, label) in &bulk.fields {
+                let selected = if bulk.field.as_ref() == Some(name) { "selected" } else { "" };
+                html_fields.push(format!(r#"<option value="{name}" {selected}>{label}</option>"#));
+            }
+
+            let html_fields = html_fields.join("\n");
+            let html_options = BootstrapRenderer::render_options(&bulk.options);
+            format!(
+                r##"
+                <select class="form-select form-select-sm w-auto" id="bulk_field-{form_id}" title="{}">
+                    {html_fields}
+                </select>
+                <input class="form-control form-control-sm w-auto" id="bulk_value-{form_id}" placeholder="{}" list="list-bulk_value-{form_id}" autocomplete="off">
+                <datalist id="list-bulk_value-{form_id}">
+                    {html_options}
+                </datalist>
+                <a href id="bulk_update-{form_id}" class="btn btn-sm btn-secondary"><i class="bi bi-pencil"></i> {}</a>
+                "##,
+                labels.bulk_field, labels.bulk_value, labels.bulk_update
+            )
+        } else {
+            "".to_string()
+        };
+
+        format!(
+            r##"
+            <div class="d-flex flex-wrap gap-2 mb-2 align-items-center">
+                <span>{selected} {} / {matching}</span>
+                <a href id="bulk_select_all-{form_id}" class="btn btn-sm btn-secondary">{}</a>
+                <a href id="bulk_select_none-{form_id}" class="btn btn-sm btn-secondary">{}</a>
+                <a href id="bulk_export-{form_id}" class="btn btn-sm btn-secondary"><i class="bi bi-download"></i> {}</a>
+                {html_remove}
+                {html_update}
+            </div>
+        "##,
+            labels.selected, labels.select_all, labels.select_none, labels.bulk_export
+        )
+    }
+
+    fn render_list_items(field: &FieldView) -> String {
+        let id = &field.id;
+        let name = &field.name;
+        let input_type = &field.input_type;
+        let step = field.step.as_ref().map(|step| format!(r#"step="{}""#, step)).unwrap_or_default();
+        let pattern = field.pattern.as_ref().map(|pattern| format!(r#"pattern="{}""#, pattern)).unwrap_or_default();
+        let max_length = field.max_length;
+        let disabled = if field.disabled { "disabled" } else { "" };
+        let items = field.list_items.clone().unwrap_or_default();
+        let mut html_items = vec![];
+
+        for (index, value) in items.iter().enumerate() {
+            let value = value.replace('"', "&quot;");
+
+            let html_buttons = if field.disabled {
+                "".to_string()
+            } else {
+                format!(
+                    r##"
+                            <a id="list_up-{id}-{index}" href class="btn btn-secondary"><i class="bi bi-arrow-up"></i></a>
+                            <a id="list_down-{id}-{index}" href class="btn btn-secondary"><i class="bi bi-arrow-down"></i></a>
+                            <a id="list_remove-{id}-{index}" href class="btn btn-secondary"><i class="bi bi-trash"></i></a>
+                    "##
+                )
+            };
+
+            html_items.push(format!(
+                r##"
+                        <div class="input-group mb-1">
+                            <input class="form-control" id="{id}-{index}" name="{name}-{index}" type="{input_type}" {step} {pattern} maxlength="{max_length}" value="{value}" {disabled}>
+                            {html_buttons}
+                        </div>
+                "##
+            ));
+        }
+
+        if !field.disabled {
+            html_items.push(format!(r##"<a id="list_add-{id}" href class="btn btn-secondary"><i class="bi bi-plus"></i></a>"##));
+        }
+
+        html_items.join("\n")
+    }
+
+    fn render_references(field: &FieldView) -> (String, String) {
+        let Some(reference) = &field.reference else {
+            return ("".to_string(), "".to_string());
+        };
+
+        let name = &field.name;
+        let view_id = &reference.view_id;
+        let mut list = vec![];
+        list.push(format!(
+            r##"<div class="col-1"><a id="{view_id}" name="reference-view-{name}" class="btn btn-secondary" href><i class="bi bi-eye-open"></i></a></div>"##
+        ));
+
+        if let Some(create_id) = &reference.create_id {
+            list.push(format!(r##"<div class="col-1"><a id="{create_id}" name="reference-create-{name}" class="btn btn-secondary" href><i class="bi bi-plus"></i></a></div>"##));
+        }
+
+        let html_external_search = if let Some(search_id) = &reference.search_id {
+            let html_external_search = format!(r##"<div class="col-1"><a id="{search_id}" name="reference-search-{name}" class="btn btn-secondary" href><i class="bi bi-search"></i></a></div>"##);
+            list.push(html_external_search.clone());
+            html_external_search
+        } else {
+            "".to_string()
+        };
+
+        (html_external_search, list.join("\n"))
+    }
+
+    fn render_flags(field: &FieldView) -> String {
+        let id = &field.id;
+        let name = &field.name;
+        let mut list = vec![];
+
+        for (index, label) in field.flags.iter().enumerate() {
+            list.push(format!(
+                r##"
+                    <div class="form-group form-group row">
+                        <label class="offset-1 control-label">
+                            <input type="checkbox" id="{id}-{index}" name="{name}-{index}"/>
+                            {label}
+                        </label>
+                    </div>
+                    "##
+            ));
+        }
+
+        list.join("\n")
+    }
+
+    fn render_field(form_type: &FormType, field: &FieldView) -> String {
+        let id = &field.id;
+        let name = &field.name;
+        let label = &field.label;
+        let range_to = &field.range_to;
+        let title = BootstrapRenderer::render_title(&field.help);
+        let placeholder = field.placeholder.clone().unwrap_or_default();
+        let input_type = &field.input_type;
+        let step = field.step.as_ref().map(|step| format!(r#"step="{}""#, step)).unwrap_or_default();
+        let html_input = BootstrapRenderer::render_control(field);
+        let (html_external_search, html_references) = BootstrapRenderer::render_references(field);
+
+        match form_type {
+            FormType::Instance => {
+                let col_size = field.col_size;
+                let html_flags = BootstrapRenderer::render_flags(field);
+
+                let html_input = if field.list_items.is_some() {
+                    format!(r#"<div id="div-list-{id}">{}</div>"#, BootstrapRenderer::render_list_items(field))
+                } else {
+                    html_input
+                };
+
+                let html_help = match &field.help {
+                    Some(help) => format!(r#"<small id="{id}-help" class="form-text text-muted">{help}</small>"#),
+                    None => "".to_string(),
+                };
+
+                let hidden = if field.hidden { "hidden" } else { "" };
+
+                format!(
+                    r##"
+                        <div id="div-{id}" class="col-{col_size}" {hidden}>
+                            <label for="{id}" class="control-label" {title}>{label}</label>
+                            <div class="row">
+                                <div id="div-control-{id}" class="col">{html_input}</div>
+                                {html_references}
+                                {html_flags}
+                            </div>
+                            {html_help}
+                        </div>
+                        "##
+                )
+            }
+            FormType::Filter => {
+                let html_field_range = if field.range_options.len() > 0 {
+                    let html_options = BootstrapRenderer::render_range_options(&field.range_options);
+                    format!(
+                        r#"
+                        <div class="form-group">
+                            <div class="offset-3 col-9">
+                                <select class="form-select" id="{id}-range" name="{name}-range">
+                                    <option value=""></option>
+                                    {html_options}
+                                </select>
+                            </div>
+                        </div>
+                        "#
+                    )
+                } else {
+                    "".to_string()
+                };
+
+                let html_input = if field.options.len() > 0 {
+                    format!(r#"<div class="col">{html_input}</div>"#)
+                } else if field.rangeable {
+                    format!(
+                        r#"
+                                    <div class="col-4">
+                                        <input class="form-control" id="{id}@min" name="{name}@min" type="{input_type}" {step} placeholder="">
+                                    </div>
+
+                                    <label for="{id}@max" class="col-1 control-label" style="text-align: center">{range_to}</label>
+
+                                    <div class="col-4">
+                                        <input class="form-control" id="{id}@max" name="{name}@max" type="{input_type}" {step} placeholder="">
+                                    </div>
+                                    "#
+                    )
+                } else {
+                    format!(
+                        r#"
+                                    <div class="col-9">
+                                        <input class="form-control" id="{id}" name="{name}" type="{input_type}" {step} placeholder="{placeholder}">
+                                    </div>
+                                    "#
+                    )
+                };
+
+                format!(
+                    r#"
+                        {html_field_range}
+                        <div class="form-group row">
+                            <label for="{id}" class="control-label col-2" {title}>{label}</label>
+                            {html_input}
+                            {html_external_search}
+                        </div>
+                    "#
+                )
+            }
+            FormType::Aggregate => {
+                let html_input = if field.range_options.len() > 0 {
+                    let html_options = BootstrapRenderer::render_range_options(&field.range_options);
+                    format!(
+                        r#"
+                        <div class="col-9">
+                            <select class="form-select" id="{id}" name="{name}">
+                                <option value=""></option>
+                                {html_options}
+                            </select>
+                        </div>
+                        "#
+                    )
+                } else if field.rangeable {
+                    format!(r#"<div class="col-4"><input  class="form-control" id="{id}" name="{name}" type="{input_type}" {step} placeholder=""></div>"#)
+                } else {
+                    format!(r#"<div class="col-4"><input  class="form-check-input" id="{id}" name="{name}" type="checkbox"></div>"#)
+                };
+
+                format!(r#"<div class="form-group row"><label for="{id}" class="control-label" {title}>{label}</label>{html_input}</div>"#)
+            }
+            FormType::Sort => {
+                format!(
+                    r#"
+                        <div class="form-group row">
+                            <label for="{id}" class="control-label" {title}>{label}</label>
+
+                            <div class="col-3">
+                                <select class="form-select" id="{id}-order_by" name="{name}-order_by">
+                                    <option value="asc">asc</option>
+                                    <option value="desc">desc</option>
+                                </select>
+                            </div>
+
+                            <div class="col-3">
+                                <input  class="form-control" id="{id}-index" name="{name}-index" type="number" step="1">
+                            </div>
+
+                            <div class="col-3">
+                                <input  class="form-check-input" id="{id}-table_visible" name="{name}-table_visible" type="checkbox">
+                            </div>
+                        </div>
+                    "#
+                )
+            }
+        }
+    }
+
+    // tabs, groups and rows keep the order of their first field, fields without row share the row of the group
+    fn render_layout(form: &FormView) -> String {
+        if form.form_type != FormType::Instance || form.fields.iter().all(|field| field.tab.is_none() && field.group.is_none() && field.row.is_none()) {
+            return form.fields.iter().map(|field| BootstrapRenderer::render_field(&form.form_type, field)).collect::<Vec<String>>().join("\n");
+        }
+
+        let mut tabs: IndexMap<&Option<String>, IndexMap<&Option<String>, IndexMap<&Option<usize>, Vec<&FieldView>>>> = IndexMap::new();
+
+        for field in &form.fields {
+            tabs.entry(&field.tab).or_default().entry(&field.group).or_default().entry(&field.row).or_default().push(field);
+        }
+
+        let id = &form.id;
+        let mut html_main = vec![];
+        let mut html_nav = vec![];
+        let mut html_panes = vec![];
+
+        for (tab_index, (tab, groups)) in tabs.iter().enumerate() {
+            let mut html_groups = vec![];
+
+            for (group, rows) in groups {
+                let html_rows = rows
+                    .values()
+                    .map(|fields| {
+                        let html_fields = fields.iter().map(|field| BootstrapRenderer::render_field(&form.form_type, field)).collect::<Vec<String>>().join("\n");
+                        format!(r#"<div class="row">{html_fields}</div>"#)
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n");
+
+                if let Some(group) = group {
+                    html_groups.push(format!(
+                        r#"
+                        <fieldset class="border rounded p-2 mb-2">
+                            <legend class="fs-6">{group}</legend>
+                            {html_rows}
+                        </fieldset>
+                        "#
+                    ));
+                } else {
+                    html_groups.push(html_rows);
+                }
+            }
+
+            let html_groups = html_groups.join("\n");
+
+            if let Some(tab) = tab {
+                let active = if html_nav.is_empty() { "active" } else { "" };
+                let show = if html_panes.is_empty() { "show active" } else { "" };
+                html_nav.push(format!(
+                    r##"<button class="nav-link {active}" data-bs-toggle="tab" data-bs-target="#nav-{id}-{tab_index}" role="tab" type="button" aria-controls="nav-{id}-{tab_index}" id="nav-tab-{id}-{tab_index}">{tab}</button>"##
+                ));
+                html_panes.push(format!(
+                    r#"
+                    <div class="tab-pane fade {show}" id="nav-{id}-{tab_index}" role="tabpanel" aria-labelledby="nav-tab-{id}-{tab_index}" tabindex="0">
+                        {html_groups}
+                    </div>
+                    "#
+                ));
+            } else {
+                html_main.push(html_groups);
+            }
+        }
+
+        let html_main = html_main.join("\n");
+
+        if html_nav.is_empty() {
+            return format!(r#"<div class="col-12">{html_main}</div>"#);
+        }
+
+        let html_nav = html_nav.join("\n");
+        let html_panes = html_panes.join("\n");
+        format!(
+            r#"
+            <div class="col-12">
+                {html_main}
+                <nav>
+                    <div class="nav nav-tabs" role="tablist">
+                        {html_nav}
+                    </div>
+                </nav>
+                <div class="tab-content">
+                    {html_panes}
+                </div>
+            </div>
+            "#
+        )
+    }
+
+    fn render_actions(actions: &Vec<ActionView>) -> String {
+        let mut list = vec![];
+
+        for action in actions {
+            let id = &action.id;
+            let name = &action.name;
+            let label = &action.label;
+            let icon = &action.icon;
+            let class = if action.primary { "btn-primary" } else { "btn-secondary" };
+            list.push(format!(r##"<button id="{id}" name="{name}" class="btn {class}"><i class="bi bi-{icon}"></i> {label}</button>"##));
+        }
+
+        list.join("\n")
+    }
+
+    fn render_search(&self, form: &FormView) -> String {
+        let form_id = &form.form_id;
+        let html_filter = form.filter.as_ref().map(|form| self.render_form(form)).unwrap_or_default();
+        let html_aggregate = form.aggregate.as_ref().map(|form| self.render_form(form)).unwrap_or_default();
+        let html_sort = form.sort.as_ref().map(|form| self.render_form(form)).unwrap_or_default();
+        let label_filter = form.filter.as_ref().map(|form| form.label.clone()).unwrap_or_default();
+        let label_aggregate = form.aggregate.as_ref().map(|form| form.label.clone()).unwrap_or_default();
+        let label_sort = form.sort.as_ref().map(|form| form.label.clone()).unwrap_or_default();
+
+        format!(
+            r##"
+                    <div class="panel panel-default">
+                        <nav>
+                            <div class="nav nav-tabs" role="tablist" id="nav-tab-{form_id}">
+                                <button class="nav-link" data-bs-toggle="tab" data-bs-target="#nav-filter-{form_id}"      role="tab" type="button" aria-controls="nav-filter-{form_id}"      aria-selected="false" id="nav-tab-filter-{form_id}">{label_filter}</button>
+                                <button class="nav-link" data-bs-toggle="tab" data-bs-target="#nav-aggregate-{form_id}"   role="tab" type="button" aria-controls="nav-aggregate-{form_id}"   aria-selected="false" id="nav-tab-aggregate-{form_id}">{label_aggregate}</button>
+                                <button class="nav-link" data-bs-toggle="tab" data-bs-target="#nav-sort-{form_id}"        role="tab" type="button" aria-controls="nav-sort-{form_id}"        aria-selected="false" id="nav-tab-sort-{form_id}">{label_sort}</button>
+                            </div>
+                        </nav>
+
+                        <div class="tab-content">
+                            <div class="tab-pane fade" id="nav-filter-{form_id}" role="tabpanel" aria-labelledby="nav-tab-filter-{form_id}" tabindex="0">
+                            {html_filter}
+                            </div>
+
+                            <div class="tab-pane fade" id="nav-aggregate-{form_id}" role="tabpanel" aria-labelledby="nav-tab-aggregate-{form_id}" tabindex="0">
+                            <canvas id="chart-aggregate-{form_id}"></canvas>
+                            {html_aggregate}
+                            </div>
+
+                            <div class="tab-pane fade" id="nav-sort-{form_id}" role="tabpanel" aria-labelledby="nav-tab-sort-{form_id}" tabindex="0">
+                            {html_sort}
+                            </div>
+                        </div>
+                    </div>
+                "##
+        )
+    }
+}
+
+impl DataViewRenderer for BootstrapRenderer {
+    fn render_form(&self, form: &FormView) -> String {
+        let id = &form.id;
+        let form_id = &form.form_id;
+        let label = &form.label;
+        let html_fields = BootstrapRenderer::render_layout(form);
+
+        if form.embedded {
+            return format!(
+                r#"
+            <fieldset id="div-{id}" class="col-12 border rounded p-2 mb-2">
+                <legend class="fs-6">{label}</legend>
+                <div class="row">
+                    {html_fields}
+                </div>
+            </fieldset>
+            "#
+            );
+        }
+        let html_actions = BootstrapRenderer::render_actions(&form.actions);
+        let html_embedded = form.childs.iter().filter(|child| child.embedded).map(|child| self.render_form(child)).collect::<Vec<String>>().join("\n");
+        let html_crud_items = form.childs.iter().filter(|child| !child.embedded).map(|child| self.render_form(child)).collect::<Vec<String>>().join("\n");
+        let hidden = if form.hidden { "hidden" } else { "" };
+
+        let (form_class, hidden_form, header, search, table) = if form.form_type == FormType::Instance {
+            let header = if let Some(href_new) = &form.href_new {
+                format!(
+                    r#"
+                    <div class="card-header">
+                        <a href="{href_new}" id="create-{id}" class="btn btn-secondary"><i class="bi bi-plus"></i> {label}</a>
+                    </div>
+                "#
+                )
+            } else {
+                format!(
+                    r#"
+                    <div class="card-header">{label}</div>
+                "#
+                )
+            };
+
+            let table = format!(
+                r#"
+                    <div id="div-table-{form_id}" class="table-responsive" style="white-space: nowrap;">
+                    </div>
+                "#
+            );
+
+            ("row", "hidden", header, self.render_search(form), table)
+        } else {
+            ("form-horizontal", "", "".to_string(), "".to_string(), "".to_string())
+        };
+
+        format!(
+            r##"
+            <div id="div-{id}" class="card" {hidden}>
+                {header}
+                <div class="card-body">
+                    <form id="{id}" name="{id}" class="{form_class}" role="form" {hidden_form}>
+                        {html_fields}
+                        {html_embedded}
+                        <div class="form-group">
+                            {html_actions}
+                        </div>
+                    </form>
+                    {html_crud_items}
+                    {search}
+                    {table}
+                </div>
+            </div>
+        "##
+        )
+    }
+
+    fn render_list(&self, field: &FieldView) -> String {
+        BootstrapRenderer::render_list_items(field)
+    }
+
+    fn render_field_control(&self, field: &FieldView) -> String {
+        BootstrapRenderer::render_control(field)
+    }
+
+    fn render_table(&self, table: &TableView) -> String {
+        let form_id = &table.form_id;
+        let labels = &table.labels;
+        let mut hmtl_header = vec![];
+
+        for column in &table.columns {
+            let field_name = &column.field_name;
+            let label = &column.label;
+            let title = BootstrapRenderer::render_title(&column.help);
+            hmtl_header.push(format!(
+                r##"
+            <th {title}>
+                <a href id="sort_left-{form_id}-{field_name}"><i class="bi bi-arrow-left"></i> </a>
+                <a href id="sort_toggle-{form_id}-{field_name}"> {label}</a>
+                <a href id="sort_rigth-{form_id}-{field_name}"><i class="bi bi-arrow-right"></i> </a>
+            </th>
+            "##
+            ));
+        }
+
+        let mut hmtl_rows = vec![];
+
+        for row in &table.rows {
+            let index = row.index;
+            let mut html_cols = vec![];
+
+            for cell in &row.cells {
+                let field_name = &cell.field_name;
+                let href = &cell.href;
+                let text = &cell.text;
+                let download = if cell.download { "download" } else { "" };
+
+                if let Some(control) = &cell.control {
+                    html_cols.push(format!(r#"<td>{}</td>"#, BootstrapRenderer::render_control(control)));
+                } else {
+                    html_cols.push(format!(r#"<td><a id="table-row-col-{form_id}-{field_name}-{index}" href="{href}" {download}>{text}</a></td>"#));
+                }
+            }
+
+            let html_cols = html_cols.join("\n");
+
+            let html_a_search_select = if let Some(select_out) = &row.select_out {
+                let item_index = row.item_index;
+                format!(r#"<a href id="search_select-{form_id}-{select_out}-{item_index}"><i class="bi bi-ok"></i> {}</a>"#, labels.select)
+            } else {
+                "".to_string()
+            };
+
+            let href_view = &row.href_view;
+
+            let html_a_edit = if let Some(href_edit) = &row.href_edit {
+                format!(r##"<a id="table-row-edit-{form_id}-{index}" href="{href_edit}"><i class="bi bi-eye-open"></i> {}</a>"##, labels.edit)
+            } else {
+                "".to_string()
+            };
+
+            let html_a_clone = if row.clonable {
+                format!(r##"<a id="table-row-clone-{form_id}-{index}" href><i class="bi bi-copy"></i> {}</a>"##, labels.clone)
+            } else {
+                "".to_string()
+            };
+
+            let html_a_restore = if row.restorable {
+                format!(r##"<a id="table-row-restore-{form_id}-{index}" href><i class="bi bi-arrow-counterclockwise"></i> {}</a>"##, labels.restore)
+            } else {
+                "".to_string()
+            };
+
+            let href_item_move = if row.movable {
+                format!(
+                    r##"
+            <a id="table-row-remove-{form_id}-{index}" href><i class="bi bi-trash"></i> {}</a>
+            <a id="table-row-up-{form_id}-{index}"     href><i class="bi bi-arrow-up"></i> {}</a>
+            <a id="table-row-down-{form_id}-{index}"   href><i class="bi bi-arrow-down"></i> {}</a>
+            "##,
+                    labels.remove, labels.up, labels.down
+                )
+            } else {
+                "".to_string()
+            };
+
+            let html_select = match row.selected {
+                Some(selected) => {
+                    let checked = if selected { "checked" } else { "" };
+                    format!(r#"<input class="form-check-input" type="checkbox" id="bulk_select-{form_id}-{index}" {checked}>"#)
+                }
+                None => "".to_string(),
+            };
+
+            hmtl_rows.push(format!(
+                r##"
+            <tr>
+                <td>
+                    {html_select}
+                    <a id="table-row-view-{form_id}-{index}" href="{href_view}"><i class="bi bi-eye-open"></i> {}</a>
+                    {html_a_edit}
+                    {html_a_clone}
+                    {html_a_restore}
+                    {html_a_search_select}
+                    {href_item_move}
+                </td>
+                {html_cols}
+            </tr>
+            "##,
+                labels.view
+            ));
+        }
+
+        let html_page_control = if let Some(pagination) = &table.pagination {
+            let mut html_pages = vec![];
+
+            for page in &pagination.pages {
+                html_pages.push(format!(r##"<li class="page-item"><a class="page-link" id="selected_page-{form_id}-{page}" href="#">{page}</a></li>"##));
+            }
+
+            let html_pages = html_pages.join("\n");
+            let page_size = pagination.page_size;
+            let label_page_size = &labels.page_size;
+            format!(
+                r##"
+            <nav aria-label="Page navigation">
+                <ul class="pagination">
+                    <li class="page-item">
+                        <a class="page-link" href="#" aria-label="Previous">
+                            <span aria-hidden="true">&laquo;</span>
+                        </a>
+                    </li>
+                    {html_pages}
+                    <li class="page-item">
+                        <a class="page-link" href="#" aria-label="Next">
+                            <span aria-hidden="true">&raquo;</span>
+                        </a>
+                    </li>
+                </ul>
+            </nav>
+
+            <div class="form-group row">
+                <label for="page_size-{form_id}" class="col-2 col-form-label">{label_page_size}</label>
+
+                <div class="col-2">
+                    <input class="form-control" id="page_size-{form_id}" name="page_size" type="number" step="1" value="{page_size}">
+                </div>
+            </div>
+            "##
+            )
+        } else {
+            "".to_string()
+        };
+
+        let html_row_add = if table.editable {
+            format!(r##"<a id="table-row-add-{form_id}" href class="btn btn-secondary"><i class="bi bi-plus"></i> {}</a>"##, labels.add)
+        } else {
+            "".to_string()
+        };
+
+        let html_bulk = if let Some(bulk) = &table.bulk {
+            BootstrapRenderer::render_bulk(form_id, bulk, labels)
+        } else {
+            "".to_string()
+        };
+
+        let html_trash = match table.trash {
+            Some(true) => format!(r##"<a id="trash-{form_id}" href class="btn btn-sm btn-secondary"><i class="bi bi-list"></i> {}</a>"##, labels.trash_exit),
+            Some(false) => format!(r##"<a id="trash-{form_id}" href class="btn btn-sm btn-secondary"><i class="bi bi-trash"></i> {}</a>"##, labels.trash),
+            None => "".to_string(),
+        };
+
+        let html_header = hmtl_header.join("\n");
+        let html_rows = hmtl_rows.join("\n");
+        format!(
+            r##"
+            {html_trash}
+            {html_bulk}
+            <table id="table-{form_id}" class="table table-responsive table-bordered table-striped clearfix">
+                <thead>
+                    <tr>
+                        <th></th>
+                        {html_header}
+                    </tr>
+                </thead>
+                <tbody>
+                {html_rows}
+                </tbody>
+            </table>
+            {html_row_add}
+            {html_page_control}
+        "##
+        )
+    }
+
+    fn render_navigation(&self, navigation: &NavigationView) -> String {
+        let mut html_items = vec![];
+
+        for item in &navigation.items {
+            let index = item.index;
+            let label = &item.label;
+
+            if item.active {
+                html_items.push(format!(r#"<li class="breadcrumb-item active" aria-current="page">{label}</li>"#));
+            } else {
+                html_items.push(format!(r#"<li class="breadcrumb-item"><a href id="navigation-{index}" data-index="{index}">{label}</a></li>"#));
+            }
+        }
+
+        let html_back = if let Some(label) = &navigation.back {
+            format!(r#"<a href id="navigation-back" class="btn btn-sm btn-secondary me-2"><i class="bi bi-arrow-left"></i> {label}</a>"#)
+        } else {
+            "".to_string()
+        };
+
+        let html_forward = if let Some(label) = &navigation.forward {
+            format!(r#"<a href id="navigation-forward" class="btn btn-sm btn-secondary ms-2">{label} <i class="bi bi-arrow-right"></i></a>"#)
+        } else {
+            "".to_string()
+        };
+
+        let html_items = html_items.join("\n");
+        format!(
+            r##"
+            <nav aria-label="breadcrumb" class="d-flex align-items-baseline">
+                {html_back}
+                <ol class="breadcrumb mb-0">
+                {html_items}
+                </ol>
+                {html_forward}
+            </nav>
+        "##
+        )
+    }
+}
@@ -1,6 +1,21 @@
 import init, { DataViewManager } from '../rufs_nfe_rust.js';
 
 let dataViewManager;
+// texts of the locale catalog, by key
+let labels = null;
+
+function translate(key) {
+	return labels?.get(key) ?? key;
+}
+
+// the static texts of the page carry their catalog key in data-label
+function applyLabels(locale) {
+	labels = DataViewManager.labels(locale);
+
+	for (let element of document.querySelectorAll('[data-label]')) {
+		element.textContent = translate(element.dataset.label);
+	}
+}
 
 function updateChanges(event, changes) {
 	if (changes == null) {
@@ -29,7 +44,13 @@ function updateChanges(event, changes) {
 			}
 
 			if (Array.isArray(value)) {
-				console.error(`Unexpected array fild (${fieldName})`);
+				for (let index = 0; index < value.length; index++) {
+					const element = form[`${fieldName}-${index}`];
+
+					if (element != null && event.target != element) {
+						element.value = value[index];
+					}
+				}
 			} else if (typeof value === 'object') {
 				console.error(`Unexpected array fild (${fieldName})`);
 /*
@@ -49,6 +70,21 @@ function updateChanges(event, changes) {
 					}
 				}
 	*/
+			} else if (form[fieldName] != null && form[fieldName].type == "file") {
+				const element = form[fieldName];
+				const preview = document.getElementById(`${element.id}-preview`);
+
+				if (preview != null) {
+					const href = element.dataset.encoding == "byte" && value.length > 0 ? `data:${element.dataset.mediaType || "application/octet-stream"};base64,${value}` : value;
+
+					if (preview.tagName == "IMG") {
+						preview.src = href;
+					} else {
+						preview.href = href;
+					}
+
+					preview.hidden = value.length == 0;
+				}
 			} else {
 				const element = form[fieldName];
 				
@@ -81,9 +117,112 @@ function updateChanges(event, changes) {
 	}
 }
 
+function updateLists(lists) {
+	if (lists == null) {
+		return;
+	}
+
+	for (let [id, html] of lists) {
+		const div = document.getElementById(`div-list-${id}`);
+
+		if (div == null) {
+			console.error(`Missing list ${id}`);
+			continue;
+		}
+
+		div.innerHTML = html;
+	}
+}
+
+// controls of cascading references, with the options narrowed by the field they depend on
+function updateOptions(options) {
+	if (options == null) {
+		return;
+	}
+
+	for (let [id, html] of options) {
+		const div = document.getElementById(`div-control-${id}`);
+
+		if (div == null) {
+			console.error(`Missing control ${id}`);
+			continue;
+		}
+
+		const element = document.getElementById(id);
+		const value = element != null ? element.value : "";
+		div.innerHTML = html;
+		document.getElementById(id).value = value;
+	}
+}
+
+// show/hide and enable/disable the fields with x-visibleIf, x-readOnlyIf and x-requiredIf
+function updateStates(states) {
+	if (states == null) {
+		return;
+	}
+
+	for (let [id, state] of states) {
+		const div = document.getElementById(`div-${id}`);
+		const element = document.getElementById(id);
+
+		if (div == null || element == null) {
+			continue;
+		}
+
+		div.hidden = state.get("hidden");
+		element.disabled = state.get("disabled");
+		element.required = state.get("required");
+	}
+}
+
+// byte fields keep the base64 content, binary fields are uploaded by DataViewManager with the same content when the record is saved
+const readFile = element => new Promise((resolve, reject) => {
+	const file = element.files[0];
+
+	if (file == null) {
+		resolve("");
+		return;
+	}
+
+	const maxSize = element.dataset.maxSize;
+
+	if (maxSize != null && element.dataset.encoding == "binary" && file.size > maxSize) {
+		reject(`File ${file.name} exceeds the maximum size of ${maxSize} bytes`);
+		return;
+	}
+
+	const reader = new FileReader();
+	reader.onload = () => {
+		const base64 = reader.result.substring(reader.result.indexOf(",") + 1);
+
+		if (maxSize != null && element.dataset.encoding == "byte" && base64.length > maxSize) {
+			reject(`File ${file.name} exceeds the maximum size of ${maxSize} encoded bytes`);
+		} else {
+			resolve(base64);
+		}
+	};
+	reader.onerror = () => reject(reader.error);
+	reader.readAsDataURL(file);
+});
+
 var appOnChange = event => {
     let element = event.target;
 
+	if (element.type == "file") {
+		readFile(element).
+		then(value => dataViewManager.process_edit_target(element.id, value)).
+		then(viewResponse => {
+			updateChanges(event, viewResponse.changes);
+			updateStates(viewResponse.states);
+		}).
+		catch(err => {
+			console.error(err);
+			document.querySelector('#http-error').innerHTML = err;
+			document.querySelector('#http-error').hidden = false;
+		});
+		return;
+	}
+
 	if (element.value == null) {
 		console.error(`appOnChange with null value in field ${element.id}`);
 		return;
@@ -97,7 +236,12 @@ var appOnChange = event => {
 	console.log(`appOnChange : ${element.id} =`, element.value);
 	dataViewManager.process_edit_target(element.id, element.value).
 	then(viewResponse => {
+		updateOptions(viewResponse.options);
 		updateChanges(event, viewResponse.changes);
+		updateLists(viewResponse.lists);
+		updateStates(viewResponse.states);
+		// the cells of the inline grids are edited inside the table
+		updateTables(viewResponse.tables);
 	}).catch(err => {
 		console.error(err);
 		document.querySelector('#http-working').hidden = true;
@@ -106,75 +250,277 @@ var appOnChange = event => {
 	});
 }
 
-var appOnClick = event => {
-    let element = event.target;
-	//event.stopPropagation();
-	event.preventDefault();
-	let target = element.id;
+// long reference lists ask for suggestions while typing, waiting for a pause in the keystrokes
+let typeaheadTimer = null;
 
-	if (element.href != null && element.href.includes("#!")) {
-		target = element.href;
+var appOnInput = event => {
+	const element = event.target;
+
+	if (element.dataset.typeahead == null) {
+		return;
 	}
 
-	if (target != null && target.length > 0 && target.startsWith("menu-") == false) {
-		console.log("appOnClick : ", target);
-		document.querySelector('#http-error').hidden = true;
-		document.querySelector('#http-working').innerHTML = "Processando...";
-		document.querySelector('#http-working').hidden = false;
-		dataViewManager.process_click_target(target).
+	clearTimeout(typeaheadTimer);
+	typeaheadTimer = setTimeout(() => {
+		dataViewManager.process_typeahead_target(element.id, element.value).
 		then(viewResponse => {
-			console.log(viewResponse);
-			// DEBUG
-			if (target == "instance-delete-new-request-requestProduct") {
-				console.log(viewResponse);
+			if (viewResponse.suggestions == null) {
+				return;
 			}
 
-			const html = viewResponse.html;
-
-			if (viewResponse.form_id != null && html != null && html.length > 0) {
-				const div_id = `data_view-${viewResponse.form_id}`;
-				let dataView = document.getElementById(div_id);
+			for (let [id, suggestions] of viewResponse.suggestions) {
+				const datalist = document.getElementById(`list-${id}`);
 
-				if (dataView != null) {
-					dataView.remove();
-					dataView = null;
+				if (datalist == null) {
+					console.error(`Missing datalist ${id}`);
+					continue;
 				}
 
-				dataView = document.createElement("div");
-				dataView.id = div_id;
-				dataView.innerHTML = html;
-				document.querySelector('#main').prepend(dataView);
+				datalist.innerHTML = suggestions.map(suggestion => `<option value="${suggestion.replaceAll('"', "&quot;")}">`).join("\n");
 			}
+		}).catch(err => console.error(err));
+	}, 250);
+}
 
-			document.querySelector('#http-working').hidden = true;
+function updateTables(tables) {
+	if (tables == null) {
+		return;
+	}
+
+	for (let [formId, html] of tables) {
+		const div = document.getElementById(`div-table-${formId}`);
+
+		if (div == null) {
+			console.error(`Missing table ${formId}`);
+			continue;
+		}
+
+		div.innerHTML = html;
+
+		if (div.hidden == true) {
+			div.hidden = false;
+		}
 
-			if (viewResponse.changes != null) {
-				updateChanges(event, viewResponse.changes);
+		const divForm = document.getElementById(`div-instance-${formId}`);
+
+		if (divForm != null && divForm.hidden == true) {
+			divForm.hidden = false;
+		}
+	}
+}
+
+function updateViewResponse(event, viewResponse, fromHistory) {
+	console.log(viewResponse);
+
+	if (viewResponse.closed != null) {
+		for (let formId of viewResponse.closed) {
+			const dataView = document.getElementById(`data_view-${formId}`);
+
+			if (dataView != null) {
+				dataView.remove();
 			}
+		}
+	}
 
-			if (viewResponse.tables != null) {
-				for (let [formId, html] of viewResponse.tables) {
-					const div = document.getElementById(`div-table-${formId}`);
-			
-					if (div == null) {
-						console.error(`Missing table ${formId}`);
-						continue;
-					}
-			
-					div.innerHTML = html;
-					
-					if (div.hidden == true) {
-						div.hidden = false;
-					}
+	const html = viewResponse.html;
 
-					const divForm = document.getElementById(`div-instance-${formId}`);
-					
-					if (divForm != null && divForm.hidden == true) {
-						divForm.hidden = false;
-					}
-				}
+	if (viewResponse.form_id != null && html != null && html.length > 0) {
+		const div_id = `data_view-${viewResponse.form_id}`;
+		let dataView = document.getElementById(div_id);
+
+		if (dataView != null) {
+			dataView.remove();
+			dataView = null;
+		}
+
+		dataView = document.createElement("div");
+		dataView.id = div_id;
+		dataView.innerHTML = html;
+		document.querySelector('#main').prepend(dataView);
+	}
+
+	document.querySelector('#http-working').hidden = true;
+
+	if (viewResponse.save_results != null) {
+		const errors = [];
+
+		for (let saveResult of viewResponse.save_results) {
+			if (saveResult.get("error") != null) {
+				errors.push(`${saveResult.get("form_id")} : ${saveResult.get("error")}`);
 			}
-		}).catch(err => {
+		}
+
+		if (errors.length > 0) {
+			document.querySelector('#http-error').innerHTML = errors.join("<br>");
+			document.querySelector('#http-error').hidden = false;
+		}
+	}
+
+	// the controls are replaced before receiving the values, that may be new options
+	updateOptions(viewResponse.options);
+
+	if (viewResponse.changes != null) {
+		updateChanges(event, viewResponse.changes);
+	}
+
+	updateLists(viewResponse.lists);
+	updateStates(viewResponse.states);
+
+	updateTables(viewResponse.tables);
+	updateNavigation(viewResponse.navigation, fromHistory);
+	updateDownload(viewResponse.download);
+	updateBulk(event, viewResponse.bulk);
+	updateConfirm(event, viewResponse.confirm);
+}
+
+// actions like delete wait for the user, that reads what is going to happen
+function updateConfirm(event, confirm) {
+	if (confirm == null || window.confirm(confirm.get("message")) == false) {
+		return;
+	}
+
+	dataViewManager.process_click_target(confirm.get("target")).
+	then(viewResponse => updateViewResponse(event, viewResponse, false)).
+	catch(err => {
+		console.error(err);
+		document.querySelector('#http-error').innerHTML = err;
+		document.querySelector('#http-error').hidden = false;
+	});
+}
+
+// the bulk operations are sent in batches, each one continued after showing its progress
+function updateBulk(event, bulk) {
+	if (bulk == null) {
+		return;
+	}
+
+	const index = bulk.get("index");
+	const percent = Math.round(100 * index / bulk.get("count"));
+	const working = document.querySelector('#http-working');
+	working.innerHTML =
+		`<div>${bulk.get("operation")} : ${index} / ${bulk.get("count")} - ${bulk.get("summary")}</div>
+		<div class="progress" role="progressbar" aria-valuenow="${percent}" aria-valuemin="0" aria-valuemax="100">
+			<div class="progress-bar" style="width: ${percent}%">${percent}%</div>
+		</div>`;
+	working.hidden = false;
+
+	if (bulk.get("finished") == true) {
+		working.innerHTML = bulk.get("summary");
+		return;
+	}
+
+	dataViewManager.process_click_target(`bulk_continue-${bulk.get("form_id")}`).
+	then(viewResponse => updateViewResponse(event, viewResponse, false)).
+	catch(err => {
+		console.error(err);
+		working.hidden = true;
+		document.querySelector('#http-error').innerHTML = err;
+		document.querySelector('#http-error').hidden = false;
+	});
+}
+
+function updateDownload(download) {
+	if (download == null) {
+		return;
+	}
+
+	const blob = new Blob([download.get("content")], {type: download.get("media_type")});
+	const a = document.createElement("a");
+	a.href = URL.createObjectURL(blob);
+	a.download = download.get("name");
+	a.click();
+	URL.revokeObjectURL(a.href);
+}
+
+let navigationIndex = null;
+// the navigation stack is rebuilt on login and on group owner switch, older history entries point to a stack that is gone
+let navigationSession = null;
+
+function resetNavigation() {
+	navigationIndex = null;
+	navigationSession = Date.now();
+}
+
+// breadcrumbs, visible form and browser history follow the navigation stack of dataViewManager
+function updateNavigation(navigation, fromHistory) {
+	if (navigation == null) {
+		return;
+	}
+
+	const formId = navigation.get("form_id");
+	document.querySelector('#navigation').innerHTML = navigation.get("html");
+
+	for (let dataView of document.querySelectorAll('#main > div[id^="data_view-"]')) {
+		dataView.hidden = dataView.id != `data_view-${formId}`;
+	}
+
+	const index = navigation.get("index");
+
+	if (fromHistory != true) {
+		const state = {index, session: navigationSession};
+
+		if (navigationIndex == null || index > navigationIndex) {
+			history.pushState(state, "", navigation.get("hash"));
+		} else {
+			history.replaceState(state, "", navigation.get("hash"));
+		}
+	}
+
+	navigationIndex = index;
+}
+
+var appOnNavigation = event => {
+	const element = event.target.closest("a");
+
+	if (element == null) {
+		return;
+	}
+
+	event.preventDefault();
+
+	if (element.id == "navigation-back") {
+		history.back();
+	} else if (element.id == "navigation-forward") {
+		history.forward();
+	} else if (element.dataset.index != null && navigationIndex != null) {
+		history.go(Number(element.dataset.index) - navigationIndex);
+	}
+}
+
+var appOnPopState = event => {
+	if (dataViewManager == null || event.state == null || event.state.index == null || event.state.session != navigationSession) {
+		return;
+	}
+
+	dataViewManager.process_click_target(`navigation-${event.state.index}`).
+	then(viewResponse => updateViewResponse(event, viewResponse, true)).
+	catch(err => {
+		console.error(err);
+		document.querySelector('#http-error').innerHTML = err;
+		document.querySelector('#http-error').hidden = false;
+	});
+}
+
+var appOnClick = event => {
+    let element = event.target;
+	//event.stopPropagation();
+	event.preventDefault();
+	let target = element.id;
+	// the resolved href of the empty ones is the current location, that is a form hash since the navigation is in the history
+	const href = element.getAttribute("href");
+
+	if (href != null && href.includes("#!")) {
+		target = href;
+	}
+
+	if (target != null && target.length > 0 && target.startsWith("menu-") == false) {
+		console.log("appOnClick : ", target);
+		document.querySelector('#http-error').hidden = true;
+		document.querySelector('#http-working').innerHTML = translate("http_working");
+		document.querySelector('#http-working').hidden = false;
+		dataViewManager.process_click_target(target).
+		then(viewResponse => updateViewResponse(event, viewResponse, false)).
+		catch(err => {
 			console.error(err);
 			document.querySelector('#http-working').hidden = true;
 			document.querySelector('#http-error').innerHTML = err;
@@ -186,7 +532,7 @@ var appOnClick = event => {
 
 var login = event => {
 	document.querySelector('#http-error').hidden = true;
-	document.querySelector('#http-working').innerHTML = "Aguardando resposta do servidor...";
+	document.querySelector('#http-working').innerHTML = translate("login_waiting");
 	document.querySelector('#http-working').hidden = false;
     let element = event.target; let rowIndex = 0;
 	const form = element.form;
@@ -196,9 +542,22 @@ var login = event => {
 		event.preventDefault();
 		const path = window.location.origin;// + window.location.pathname;
 		dataViewManager = new DataViewManager(path);
+		resetNavigation();
 		const user = form.user.value;
 		const password = form.password.value;
-		dataViewManager.login("/login", user, password).
+		const locale = form.locale.value;
+		// the progress is a struct, that comes as a plain object
+		const loginProgress = progress => {
+			const index = progress.index + (progress.finished ? 1 : 0);
+			const percent = Math.round(100 * index / progress.count);
+			const detail = progress.finished ? `${progress.rows} ${translate("login_rows")}, ${progress.bytes} bytes` : translate("login_loading");
+			document.querySelector('#http-working').innerHTML =
+				`<div>${progress.schema_name} : ${detail}</div>
+				<div class="progress" role="progressbar" aria-valuenow="${percent}" aria-valuemin="0" aria-valuemax="100">
+					<div class="progress-bar" style="width: ${percent}%">${percent}%</div>
+				</div>`;
+		};
+		dataViewManager.login("/login", user, password, locale, loginProgress).
 		then(loginResponse => {
 			const addToParent = (menu, list) => {
 				for (let [name, field] of menu) {
@@ -222,6 +581,31 @@ var login = event => {
 			div.innerHTML = str;
 			div.addEventListener('click', appOnClick);
 			document.querySelector('#menu').appendChild(div);
+			const groupOwners = loginResponse.get("group_owners");
+
+			if (groupOwners != null && groupOwners.length > 0) {
+				const options = groupOwners.map(item => `<option value="${item.get("id")}">${item.get("name")}</option>`);
+				const divGroupOwner = document.createElement("div");
+				divGroupOwner.innerHTML = `<select class="form-select" id="group_owner-active">${options.join("\n")}</select>`;
+				divGroupOwner.querySelector('select').value = loginResponse.get("rufs_group_owner");
+				divGroupOwner.addEventListener('change', event => {
+					dataViewManager.process_edit_target(event.target.id, event.target.value).
+					then(() => {
+						document.querySelector('#main').innerHTML = "";
+						resetNavigation();
+
+						for (let element of document.querySelectorAll(`a[href='#!/app/${loginResponse.get("path")}']`)) {
+							element.click();
+						}
+					}).catch(err => {
+						console.error(err);
+						document.querySelector('#http-error').innerHTML = err;
+						document.querySelector('#http-error').hidden = false;
+					});
+				});
+				document.querySelector('#menu').appendChild(divGroupOwner);
+			}
+
 			form.hidden = true;
 			document.querySelector('#http-working').hidden = true;
 
@@ -239,9 +623,15 @@ var login = event => {
 
 async function run() {
 	await init();
+	const selectLocale = document.querySelector('#login-locale');
+	applyLabels(selectLocale.value);
+	selectLocale.addEventListener('change', event => applyLabels(event.target.value));
 	document.querySelector('#login-send').addEventListener('click', login);
 	document.querySelector('#main').addEventListener('click', appOnClick);
 	document.querySelector('#main').addEventListener('change', appOnChange);
+	document.querySelector('#main').addEventListener('input', appOnInput);
+	document.querySelector('#navigation').addEventListener('click', appOnNavigation);
+	window.addEventListener('popstate', appOnPopState);
 }
 
 run();